@@ -5,7 +5,7 @@ import { SystemProgram, PublicKey, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
 import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from '@solana/spl-token';
 import { connection, program, globalAccountPDAAddress, proposalSeed, programId, proposalEscrowSeed } from '../config';
 import { BN } from 'bn.js';
-import { Loader2, CheckCircle, Coins, ToggleLeft, ToggleRight } from 'lucide-react';
+import { Loader2, CheckCircle, Coins, ToggleLeft, ToggleRight, Zap } from 'lucide-react';
 
 const CreateProposal = () => {
     // ... (state vars) 
@@ -21,6 +21,7 @@ const CreateProposal = () => {
 
   // Treasury proposal state
   const [isTreasuryProposal, setIsTreasuryProposal] = useState(false);
+  const [isEmergency, setIsEmergency] = useState(false); // Only emergencies can be fast-tracked by the council
   const [transferAmount, setTransferAmount] = useState('');
   const [destinationAddress, setDestinationAddress] = useState('');
   const [timelockSeconds, setTimelockSeconds] = useState('60'); // Default 60 seconds for demo
@@ -144,7 +145,8 @@ const CreateProposal = () => {
             deadlineTimestamp, 
             amountInLamports, 
             destination,
-            new BN(timelockSeconds)
+            new BN(timelockSeconds),
+            isEmergency
           )
           .accounts({
             globalAccount: globalAccountPDAAddress,
@@ -161,7 +163,7 @@ const CreateProposal = () => {
       } else {
         // Standard Proposal - no treasury
         transaction = await votingProgram.methods
-          .createProposal(proposalTitle, proposalDescription, deadlineTimestamp)
+          .createProposal(proposalTitle, proposalDescription, deadlineTimestamp, isEmergency)
           .accounts({
             globalAccount: globalAccountPDAAddress,
             proposalAccount: proposalPDAAddress,
@@ -277,6 +279,35 @@ const CreateProposal = () => {
           </p>
         </div>
 
+        {/* Emergency Toggle */}
+        <div className="border-t border-white/10 pt-6">
+          <button
+            type="button"
+            onClick={() => setIsEmergency(!isEmergency)}
+            disabled={loading}
+            className={`w-full flex items-center justify-between p-4 rounded-xl border transition-all ${
+              isEmergency 
+                ? 'bg-amber-500/10 border-amber-500/30' 
+                : 'bg-[#1a1c2e] border-white/10 hover:border-white/20'
+            }`}
+          >
+            <div className="flex items-center gap-3">
+              <Zap className={`w-5 h-5 ${isEmergency ? 'text-amber-400' : 'text-gray-400'}`} />
+              <div className="text-left">
+                <span className={`font-medium ${isEmergency ? 'text-amber-400' : 'text-white'}`}>
+                  Emergency
+                </span>
+                <p className="text-xs text-gray-500">A council majority may shorten its vote and skip the timelock</p>
+              </div>
+            </div>
+            {isEmergency ? (
+              <ToggleRight className="w-8 h-8 text-amber-400" />
+            ) : (
+              <ToggleLeft className="w-8 h-8 text-gray-500" />
+            )}
+          </button>
+        </div>
+
         {/* Treasury Toggle */}
         <div className="border-t border-white/10 pt-6">
           <button
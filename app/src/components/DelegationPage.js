@@ -1,17 +1,22 @@
 import React, { useState, useEffect } from 'react';
 import { useWallet } from '@solana/wallet-adapter-react';
-import { program, programId, globalAccountPDAAddress, delegateProfileSeed, delegationRecordSeed } from '../config';
+import { program, programId, globalAccountPDAAddress, delegateProfileSeed, delegationRecordSeed, daoConfigSeed, rewardPoolSeed, rewardVaultSeed, stakerProfilePDA, fetchStakePositions, positionAccounts } from '../config';
 import { PublicKey, SystemProgram } from '@solana/web3.js';
+import { getAssociatedTokenAddressSync } from '@solana/spl-token';
 import { Shield, User, Award, CheckCircle, AlertTriangle, Loader2 } from 'lucide-react';
 import * as anchor from "@coral-xyz/anchor";
 
+// Delegations never expire from the UI and cover every proposal type
+const NO_EXPIRY = 0;
+const SCOPE_ALL = 0xFFFFFFFF;
+
 const DelegationPage = () => {
   const wallet = useWallet();
   const { publicKey } = wallet;
   
   const [isAdmin, setIsAdmin] = useState(false);
   const [delegates, setDelegates] = useState([]);
-  const [myDelegate, setMyDelegate] = useState(null);
+  const [myDelegates, setMyDelegates] = useState([]);
   const [isDelegateMyself, setIsDelegateMyself] = useState(false); // Track if I am a delegate
   const [loading, setLoading] = useState(true);
   const [actionLoading, setActionLoading] = useState(false);
@@ -50,9 +55,9 @@ const DelegationPage = () => {
                 
                 try {
                     const record = await daoProgram.account.delegationRecord.fetch(myRecordPDA);
-                    setMyDelegate(record.delegateTarget.toString());
+                    setMyDelegates(record.shares.map(share => share.delegate.toString()));
                 } catch(e) {
-                    setMyDelegate(null); // No delegation found
+                    setMyDelegates([]); // No delegation found
                 }
             }
 
@@ -94,15 +99,53 @@ const DelegationPage = () => {
             [Buffer.from(delegationRecordSeed), publicKey.toBuffer()],
             programId
         );
+        const record = await daoProgram.account.delegationRecord.fetch(recordPDA);
+        const globalAccount = await daoProgram.account.globalAccount.fetch(globalAccountPDAAddress);
+        const tokenMint = globalAccount.tokenMint;
+        const mintInfo = await daoProgram.provider.connection.getAccountInfo(tokenMint);
+        const tokenProgram = mintInfo.owner;
+        const [rewardVaultPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from(rewardVaultSeed), tokenMint.toBuffer()],
+            programId
+        );
+
+        // Each delegate's (profile, token account) pair receives the commission
+        // charged so far, followed by every position the user opened
+        const commissionAccounts = record.shares.flatMap(share => [
+            {
+                pubkey: PublicKey.findProgramAddressSync(
+                    [Buffer.from(delegateProfileSeed), share.delegate.toBuffer()],
+                    programId
+                )[0],
+                isWritable: true,
+                isSigner: false,
+            },
+            {
+                pubkey: getAssociatedTokenAddressSync(tokenMint, share.delegate, false, tokenProgram),
+                isWritable: true,
+                isSigner: false,
+            },
+        ]);
+        const positions = await fetchStakePositions(daoProgram, publicKey);
+
+        // The reward vault accounts are only passed once rewards exist to pay from
+        const hasRewardVault = !!(await daoProgram.provider.connection.getAccountInfo(rewardVaultPDA));
 
         await daoProgram.methods.revokeDelegation()
           .accounts({
               delegationRecord: recordPDA,
+              daoConfig: PublicKey.findProgramAddressSync([Buffer.from(daoConfigSeed)], programId)[0],
+              stakerProfile: stakerProfilePDA(publicKey),
+              rewardPool: PublicKey.findProgramAddressSync([Buffer.from(rewardPoolSeed)], programId)[0],
+              rewardVault: hasRewardVault ? rewardVaultPDA : null,
+              tokenMint: hasRewardVault ? tokenMint : null,
+              tokenProgram: hasRewardVault ? tokenProgram : null,
               user: publicKey,
           })
+          .remainingAccounts([...commissionAccounts, ...positionAccounts(positions, publicKey)])
           .rpc();
         
-        setMyDelegate(null);
+        setMyDelegates([]);
     } catch(e) {
        console.error("Revocation failed", e);
        alert("Failed: " + e.message);
@@ -123,16 +166,34 @@ const DelegationPage = () => {
               programId
           );
 
-          await daoProgram.methods.delegateVote()
+          const [targetProfilePDA] = PublicKey.findProgramAddressSync(
+              [Buffer.from(delegateProfileSeed), targetPubkey.toBuffer()],
+              programId
+          );
+          const [userProfilePDA] = PublicKey.findProgramAddressSync(
+              [Buffer.from(delegateProfileSeed), publicKey.toBuffer()],
+              programId
+          );
+
+          // Every position the user opened, in position id order
+          const positions = await fetchStakePositions(daoProgram, publicKey);
+
+          await daoProgram.methods.delegateVote({ expiresAt: new anchor.BN(NO_EXPIRY), scope: SCOPE_ALL })
             .accounts({
                 delegationRecord: recordPDA,
                 targetDelegate: targetPubkey,
+                targetDelegateProfile: targetProfilePDA,
+                userDelegateProfile: userProfilePDA,
+                daoConfig: PublicKey.findProgramAddressSync([Buffer.from(daoConfigSeed)], programId)[0],
+                stakerProfile: stakerProfilePDA(publicKey),
+                rewardPool: PublicKey.findProgramAddressSync([Buffer.from(rewardPoolSeed)], programId)[0],
                 user: publicKey,
                 systemProgram: SystemProgram.programId
             })
+            .remainingAccounts(positionAccounts(positions, publicKey))
             .rpc();
           
-          setMyDelegate(targetAddr);
+          setMyDelegates([targetAddr]);
       } catch(e) {
          console.error("Delegation failed", e);
          alert("Failed: " + e.message);
@@ -161,7 +222,7 @@ const DelegationPage = () => {
           </p>
         </div>
         
-        {myDelegate && (
+        {myDelegates.length > 0 && (
              <div className="bg-pulsar-surface border border-pulsar-primary/30 px-6 py-3 rounded-xl flex items-center gap-3">
                  <div className="bg-pulsar-primary/20 p-2 rounded-full">
                      <Shield className="text-pulsar-primary" size={20} />
@@ -169,7 +230,7 @@ const DelegationPage = () => {
                  <div>
                      <div className="text-xs text-pulsar-muted uppercase font-bold">Currently Delegating To</div>
                      <div className="font-mono text-sm text-white">
-                         {myDelegate.slice(0,4)}...{myDelegate.slice(-4)}
+                         {myDelegates.map(d => `${d.slice(0,4)}...${d.slice(-4)}`).join(', ')}
                      </div>
                  </div>
              </div>
@@ -200,7 +261,7 @@ const DelegationPage = () => {
         ) : (
           <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-6">
               {delegates.map((d) => {
-                  const isMyDelegate = myDelegate === d.address;
+                  const isMyDelegate = myDelegates.includes(d.address);
                   return (
                       <div key={d.address} className={`bg-pulsar-surface border ${isMyDelegate ? 'border-pulsar-primary shadow-[0_0_20px_rgba(20,241,149,0.1)]' : 'border-white/10'} p-6 rounded-2xl transition-all hover:border-white/20 relative group`}>
                           
@@ -219,7 +280,7 @@ const DelegationPage = () => {
                           <div className="flex gap-3">
                               <button 
                                 onClick={() => handleDelegateVote(d.address)}
-                                disabled={actionLoading || isMyDelegate || isDelegateMyself}
+                                disabled={actionLoading || myDelegates.length > 0 || isDelegateMyself}
                                 className={`flex-1 py-3 rounded-xl font-bold text-sm transition-all ${
                                     isMyDelegate 
                                     ? 'bg-pulsar-primary/20 text-pulsar-primary cursor-default'
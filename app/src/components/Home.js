@@ -15,6 +15,8 @@ import {
   proposalSeed,
   delegationRecordSeed,
   delegateProfileSeed,
+  fetchStakePositions,
+  votingPower as calculateVotingPower,
 } from '../config';
 import Star from './Star';
 import { ArrowRight, Users } from 'lucide-react';
@@ -34,13 +36,11 @@ const Home = () => {
   const [delegatedTo, setDelegatedTo] = useState(null); 
   const [isDidDelegate, setIsDidDelegate] = useState(false); // Am I a delegate?
   const [tokenMint, setTokenMint] = useState(null);
-  const [stats, setStats] = useState({ liquid: 0, staked: 0, multiplier: 1 });
+  const [stats, setStats] = useState({ liquid: 0, staked: 0, multiplier: 1, stakedPower: 0 });
   const [globalError, setGlobalError] = useState(null);
   const [activeTab, setActiveTab] = useState('active');
 
 
-  const STAKE_RECORD_SEED = "stake_record";
-
   // --- FETCH LOGIC ---
   useEffect(() => {
     const fetchAllData = async () => {
@@ -79,8 +79,7 @@ const Home = () => {
            // 2. Fetch User Voting Power (if connected && mint exists)
            if (publicKey && mintAddr) {
                let liquidAmount = 0;
-               let stakedAmount = 0;
-               let multiplier = 1;
+               let positions = [];
 
                // A. Liquid Tokens
                try {
@@ -89,21 +88,20 @@ const Home = () => {
                    liquidAmount = Number(accountInfo.amount);
                } catch(e) { /* No tokens or account not found -> 0 */ }
 
-               // B. Staked Tokens
+               // B. Staked Tokens (every position)
                try {
-                   const [stakeRecordPDA] = PublicKey.findProgramAddressSync(
-                        [Buffer.from(STAKE_RECORD_SEED), publicKey.toBuffer()],
-                        programId
-                    );
-                    const record = await votingProgram.account.voterStakeRecord.fetch(stakeRecordPDA);
-                    stakedAmount = record.stakedAmount.toNumber();
-                    multiplier = record.multiplier.toNumber();
-               } catch(e) { /* No stake record -> 0 */ }
+                    positions = await fetchStakePositions(votingProgram, publicKey);
+               } catch(e) { /* No stake positions -> 0 */ }
                
-               // Match Rust: floor(sqrt(liquid)) + floor(sqrt(staked)) * multiplier
-               const totalVP = Math.floor(Math.sqrt(liquidAmount)) + Math.floor(Math.sqrt(stakedAmount)) * multiplier;
-               setVotingPower(totalVP);
-               setStats({ liquid: liquidAmount, staked: stakedAmount, multiplier });
+               // Match Rust `calculate_voting_power`
+               const power = calculateVotingPower(liquidAmount, positions);
+               setVotingPower(power.total);
+               setStats({
+                   liquid: liquidAmount,
+                   staked: power.staked,
+                   multiplier: Math.round(power.multiplier * 100) / 100,
+                   stakedPower: power.total - Math.floor(Math.sqrt(liquidAmount)),
+               });
 
                 // C. Check Delegation Logic
                 try {
@@ -112,7 +110,7 @@ const Home = () => {
                        programId
                    );
                    const record = await votingProgram.account.delegationRecord.fetch(delegationRecordPDA);
-                   setDelegatedTo(record.delegateTarget.toString());
+                   setDelegatedTo(record.shares.map(share => share.delegate.toString()).join(', '));
                 } catch(e) {
                    setDelegatedTo(null);
                 }
@@ -131,7 +129,7 @@ const Home = () => {
                          // 2. Find all my delegators
                          // Ideally use memcmp filter, but for now fetch all and filter JS side for simplicity/speed dev
                          const allRecords = await votingProgram.account.delegationRecord.all();
-                         const myDelegators = allRecords.filter(r => r.account.delegateTarget.toString() === publicKey.toString());
+                         const myDelegators = allRecords.filter(r => r.account.shares.some(share => share.delegate.equals(publicKey)));
                          
                          let totalDelegated = 0;
                          
@@ -139,30 +137,25 @@ const Home = () => {
                          // Parallelize for speed
                          await Promise.all(myDelegators.map(async (record) => {
                              const delegatorPubkey = record.account.delegator;
+                             const share = record.account.shares.find(s => s.delegate.equals(publicKey));
                              
                              // Get Liquid
                              let dLiquid = 0;
                              try {
                                  const dATA = await getAssociatedTokenAddress(new PublicKey(mintAddr), delegatorPubkey);
                                  const dBal = await connection.getTokenAccountBalance(dATA);
-                                 dLiquid = dBal.value.uiAmount || 0;
+                                 dLiquid = Number(dBal.value.amount);
                              } catch(e) {}
                              
                              // Get Staked
-                             let dStaked = 0;
-                             let dMult = 1;
+                             let dPositions = [];
                              try {
-                                 const [dStakePDA] = PublicKey.findProgramAddressSync(
-                                     [Buffer.from("stake_record"), delegatorPubkey.toBuffer()],
-                                     programId
-                                 );
-                                 const dStake = await votingProgram.account.voterStakeRecord.fetch(dStakePDA);
-                                 dStaked = parseFloat(dStake.stakedAmount.toString());
-                                 dMult = parseFloat(dStake.multiplier.toString());
+                                 dPositions = await fetchStakePositions(votingProgram, delegatorPubkey);
                              } catch(e) {}
                              
-                             const dVP = Math.floor(Math.sqrt(dLiquid)) + Math.floor(Math.sqrt(dStaked)) * dMult;
-                             totalDelegated += dVP;
+                             // A delegate votes its share of the delegator's power
+                             const dVP = calculateVotingPower(dLiquid, dPositions).total;
+                             totalDelegated += Math.floor(dVP * share.bps / 10000);
                          }));
                          
                          setDelegatedPower(totalDelegated);
@@ -175,7 +168,7 @@ const Home = () => {
             } else {
                 setVotingPower(0);
                 setDelegatedTo(null);
-                setStats({ liquid: 0, staked: 0, multiplier: 1 });
+                setStats({ liquid: 0, staked: 0, multiplier: 1, stakedPower: 0 });
             }
 
            // 3. Fetch Proposals
@@ -325,7 +318,7 @@ const Home = () => {
                     <div className="text-right">
                         <p className="text-emerald-400 text-sm font-bold">Staked Power</p>
                         <p className="text-white text-lg font-mono">
-                           <span className="text-gray-500 text-sm">√{stats.staked} × {stats.staked > 0 ? stats.multiplier : 1} ≈</span> {Math.floor(Math.sqrt(stats.staked)) * (stats.staked > 0 ? stats.multiplier : 1)}
+                           <span className="text-gray-500 text-sm">√{stats.staked} × {stats.staked > 0 ? stats.multiplier : 1} ≈</span> {stats.stakedPower}
                         </p>
                     </div>
                 </div>
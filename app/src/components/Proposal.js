@@ -1,13 +1,13 @@
 import React, { useEffect, useState } from 'react';
 import { useParams, useNavigate } from 'react-router-dom';
 import { useWallet, useConnection } from '@solana/wallet-adapter-react';
-import { PublicKey, Transaction } from '@solana/web3.js';
+import { PublicKey, Transaction, SystemProgram } from '@solana/web3.js';
 import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from '@solana/spl-token';
 import { CheckCircle, XCircle, ArrowLeft, Clock, Vote, AlertTriangle, Loader2, Users, Lock, Coins, ExternalLink, Play, Undo2 } from 'lucide-react';
-import { program, programId, proposalSeed, globalStateSeed, delegationRecordSeed, delegateProfileSeed, proposalEscrowSeed, userStatsSeed } from '../config';
+import { program, programId, proposalSeed, globalStateSeed, delegationRecordSeed, delegateProfileSeed, proposalEscrowSeed, userStatsSeed, fetchStakePositions, positionAccounts, votingPower as calculateVotingPower } from '../config';
 import { BN } from 'bn.js';
 
-const STAKE_RECORD_SEED = "stake_record";
+const DELEGATE_PARTICIPATION_SEED = "delegate_participation";
 
 function toLittleEndian8Bytes(num) {
     const buffer = Buffer.alloc(8);
@@ -50,29 +50,19 @@ const Proposal = () => {
                  try {
                     const userATA = await getAssociatedTokenAddress(new PublicKey(tokenMint), publicKey);
                     const bal = await connection.getTokenAccountBalance(userATA);
-                    liquidAmount = bal.value.uiAmount || 0;
+                    liquidAmount = Number(bal.value.amount);
                  } catch(e) {}
                  
-                 // 2. Staked Power
-                 let stakedAmount = 0;
-                 let multiplier = 1;
+                 // 2. Staked Power (every position)
+                 let positions = [];
                  const votingProgram = program({ publicKey }); 
                  
                  try {
-                     const [stakeRecordPDA] = PublicKey.findProgramAddressSync(
-                        [Buffer.from(STAKE_RECORD_SEED), publicKey.toBuffer()],
-                        programId
-                     );
-                     const record = await votingProgram.account.voterStakeRecord.fetch(stakeRecordPDA);
-                     stakedAmount = parseFloat(record.stakedAmount.toString());
-                     multiplier = parseFloat(record.multiplier.toString());
+                     positions = await fetchStakePositions(votingProgram, publicKey);
                  } catch(e) {}
                  
-                 // Mimic Rust's integer arithmetic exactly: floor(sqrt(a)) + floor(sqrt(b)) * m
-                 const liquidPower = Math.floor(Math.sqrt(liquidAmount));
-                 const stakedPower = Math.floor(Math.sqrt(stakedAmount)) * multiplier;
-
-                 setVotingPower(liquidPower + stakedPower);
+                 // Mimic Rust's `calculate_voting_power` exactly
+                 setVotingPower(calculateVotingPower(liquidAmount, positions).total);
 
                  // Check Delegation
                  try {
@@ -81,7 +71,7 @@ const Proposal = () => {
                         programId
                      );
                      const record = await votingProgram.account.delegationRecord.fetch(delegationRecordPDA);
-                     setDelegatedTo(record.delegateTarget.toString());
+                     setDelegatedTo(record.shares.map(share => share.delegate.toString()).join(', '));
                  } catch(e) { setDelegatedTo(null); }
 
                   // Check if I am a Delegate & Calc Delegated Power
@@ -94,30 +84,26 @@ const Proposal = () => {
                          if (profile && profile.isActive) {
                              // Fetch Delegators
                              const allRecords = await votingProgram.account.delegationRecord.all();
-                             const myDelegators = allRecords.filter(r => r.account.delegateTarget.toString() === publicKey.toString());
+                             const myDelegators = allRecords.filter(r => r.account.shares.some(share => share.delegate.equals(publicKey)));
                              setDelegatorsList(myDelegators);
                              
                              let totalDelegated = 0;
                              await Promise.all(myDelegators.map(async (record) => {
                                  const delegatorPubkey = record.account.delegator;
+                                 const share = record.account.shares.find(s => s.delegate.equals(publicKey));
                                  let dLiquid = 0;
                                  try {
                                      const dATA = await getAssociatedTokenAddress(new PublicKey(tokenMint), delegatorPubkey);
                                      const dBal = await connection.getTokenAccountBalance(dATA);
-                                     dLiquid = dBal.value.uiAmount || 0;
+                                     dLiquid = Number(dBal.value.amount);
                                  } catch(e) {}
-                                 let dStaked = 0;
-                                 let dMult = 1;
+                                 let dPositions = [];
                                  try {
-                                     const [dStakePDA] = PublicKey.findProgramAddressSync(
-                                         [Buffer.from("stake_record"), delegatorPubkey.toBuffer()],
-                                         programId
-                                     );
-                                     const dStake = await votingProgram.account.voterStakeRecord.fetch(dStakePDA);
-                                     dStaked = parseFloat(dStake.stakedAmount.toString());
-                                     dMult = parseFloat(dStake.multiplier.toString());
+                                     dPositions = await fetchStakePositions(votingProgram, delegatorPubkey);
                                  } catch(e) {}
-                                 totalDelegated += Math.floor(Math.sqrt(dLiquid)) + Math.floor(Math.sqrt(dStaked)) * dMult;
+                                 // A delegate votes its share of the delegator's power
+                                 const dVP = calculateVotingPower(dLiquid, dPositions).total;
+                                 totalDelegated += Math.floor(dVP * share.bps / 10000);
                              }));
                              setDelegatedPower(totalDelegated);
                          }
@@ -314,23 +300,11 @@ const Proposal = () => {
                     [Buffer.from('voter'), proposalPubkey.toBuffer(), publicKey.toBuffer()],
                     programId
                 );
-                const [stakeRecordPDA] = PublicKey.findProgramAddressSync(
-                    [Buffer.from(STAKE_RECORD_SEED), publicKey.toBuffer()],
-                    programId
-                );
                 const userATA = await getAssociatedTokenAddress(new PublicKey(tokenMint), publicKey);
 
-                // Check stake record existence
-                let validStakeRecord = null;
-                try {
-                    const info = await connection.getAccountInfo(stakeRecordPDA);
-                    if (info) validStakeRecord = stakeRecordPDA;
-                } catch(e) {}
-
-                const [delegationRecordPDA] = PublicKey.findProgramAddressSync(
-                    [Buffer.from(delegationRecordSeed), publicKey.toBuffer()],
-                    programId
-                );
+                // Every position holding stake counts towards the vote
+                const positions = await fetchStakePositions(votingProgram, publicKey);
+                const stakedPositions = positions.filter(p => p.held && p.account.stakedAmount.toNumber() > 0);
 
                 const ix = await votingProgram.methods
                     .vote(voteYes)
@@ -338,12 +312,12 @@ const Proposal = () => {
                         globalAccount: globalAccountPDA,
                         proposalAccount: proposalPubkey,
                         voterRecord: voterRecordPDA,
-                        stakeRecord: validStakeRecord,
                         userTokenAccount: userATA,
-                        delegationRecord: delegationRecordPDA,
                         userStats: PublicKey.findProgramAddressSync([Buffer.from(userStatsSeed), publicKey.toBuffer()], programId)[0],
                         user: publicKey,
+                        systemProgram: SystemProgram.programId,
                     })
+                    .remainingAccounts(positionAccounts(stakedPositions, publicKey))
                     .instruction();
                 
                 transaction.add(ix);
@@ -370,14 +344,14 @@ const Proposal = () => {
                         [Buffer.from('voter'), proposalPubkey.toBuffer(), delegatorPubkey.toBuffer()],
                         programId
                     );
-                    const [dStakeRecordPDA] = PublicKey.findProgramAddressSync(
-                        [Buffer.from(STAKE_RECORD_SEED), delegatorPubkey.toBuffer()],
+                    const [participationPDA] = PublicKey.findProgramAddressSync(
+                        [Buffer.from(DELEGATE_PARTICIPATION_SEED), proposalPubkey.toBuffer(), publicKey.toBuffer()],
                         programId
                     );
                     
-                    // Check if stake record exists on-chain
-                    const stakeRecordInfo = await connection.getAccountInfo(dStakeRecordPDA);
-                    const delegatorStakeRecordAccount = stakeRecordInfo ? dStakeRecordPDA : null;
+                    // The delegator's staked positions back the proxy vote
+                    const dPositions = await fetchStakePositions(votingProgram, delegatorPubkey);
+                    const dStakedPositions = dPositions.filter(p => p.held && p.account.stakedAmount.toNumber() > 0);
                     
                     // Delegator Must Have an ATA for vote to count (Liquid Power)
                     const dATA = await getAssociatedTokenAddress(new PublicKey(tokenMint), delegatorPubkey);
@@ -388,14 +362,16 @@ const Proposal = () => {
                             globalAccount: globalAccountPDA,
                             proposalAccount: proposalPubkey,
                             delegateProfile: myProfilePDA,
+                            participation: participationPDA,
                             delegationRecord: dDelegationRecordPDA,
                             voterRecord: dVoterRecordPDA,
                             delegatorTokenAccount: dATA,
-                            delegatorStakeRecord: delegatorStakeRecordAccount,
                             delegatorUser: delegatorPubkey,
                             userStats: PublicKey.findProgramAddressSync([Buffer.from(userStatsSeed), publicKey.toBuffer()], programId)[0],
                             proxyAuthority: publicKey,
+                            systemProgram: SystemProgram.programId,
                         })
+                        .remainingAccounts(positionAccounts(dStakedPositions, delegatorPubkey))
                         .instruction();
                     
                     transaction.add(proxyIx);
@@ -1,53 +1,66 @@
 /* global BigInt */
 import React, { useState, useEffect } from 'react';
 import { useWallet, useConnection } from '@solana/wallet-adapter-react';
-import * as anchor from '@coral-xyz/anchor';
-import { PublicKey, Transaction, SystemProgram, SYSVAR_RENT_PUBKEY, TransactionInstruction } from '@solana/web3.js';
-import { BN, Program, AnchorProvider, web3 } from '@coral-xyz/anchor';
-import { program, programId, globalStateSeed } from '../config';
-import { Lock, Unlock, AlertTriangle, TrendingUp, Loader2, CheckCircle } from 'lucide-react';
+import { PublicKey, Transaction, SystemProgram, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
+import { BN } from '@coral-xyz/anchor';
+import {
+    program,
+    programId,
+    globalStateSeed,
+    delegationRecordSeed,
+    vaultSeed,
+    rewardPoolSeed,
+    daoConfigSeed,
+    stakerProfilePDA,
+    stakeRecordPDA,
+    fetchStakePositions,
+    votingPower,
+} from '../config';
+import { Lock, Unlock, AlertTriangle, TrendingUp, Loader2, CheckCircle, Clock } from 'lucide-react';
 import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from '@solana/spl-token';
-import idl from '../idl/pulsar_dao.json';
 
-const STAKE_RECORD_SEED = 'stake_record';
+const MIN_LOCK_DAYS = 30;
+const MAX_LOCK_DAYS = 360;
 
 const StakeManager = ({ tokenMintAddress }) => {
-    // ... no changes in hooks ...
     const { connection } = useConnection();
     const { publicKey, sendTransaction } = useWallet();
-    const [stakeRecord, setStakeRecord] = useState(null);
+    const [positions, setPositions] = useState([]);
     const [amount, setAmount] = useState('');
     const [lockPeriod, setLockPeriod] = useState(30);
     const [userBalance, setUserBalance] = useState(0);
     const [loading, setLoading] = useState(false);
     const [decimals, setDecimals] = useState(0);
+    const [tokenProgramId, setTokenProgramId] = useState(TOKEN_PROGRAM_ID);
     const [error, setError] = useState(null);
     const [actionSuccess, setActionSuccess] = useState('');
 
-    // ... useEffects ...
     useEffect(() => {
         if (!publicKey || !tokenMintAddress) return;
 
         const fetchData = async () => {
             try {
-                // Get Token Decimals
+                // Get Token Decimals & Program (Token or Token-2022)
+                let mintProgram = TOKEN_PROGRAM_ID;
                 try {
                     const mintInfo = await connection.getParsedAccountInfo(new PublicKey(tokenMintAddress));
                     const decimals = mintInfo.value?.data?.parsed?.info?.decimals || 0;
                     setDecimals(decimals);
+                    if (mintInfo.value?.owner) mintProgram = mintInfo.value.owner;
+                    setTokenProgramId(mintProgram);
                 } catch (e) {
                     console.log("Error fetching decimals, defaulting to 0", e);
                 }
 
                 // Get User Balance
-                const userATA = await getAssociatedTokenAddress(new PublicKey(tokenMintAddress), publicKey);
+                const userATA = await getAssociatedTokenAddress(new PublicKey(tokenMintAddress), publicKey, false, mintProgram);
                 try {
                     const balance = await connection.getTokenAccountBalance(userATA);
                     setUserBalance(balance.value.uiAmount || 0);
                 } catch (e) {
                     setUserBalance(0);
                 }
-                 fetchStakeRecord();
+                 fetchPositions();
             } catch (err) {
                 console.error("Error fetching data:", err);
             }
@@ -58,68 +71,102 @@ const StakeManager = ({ tokenMintAddress }) => {
         return () => clearInterval(interval);
     }, [publicKey, tokenMintAddress, connection]);
 
-    useEffect(() => {
-        if (stakeRecord) {
-             const currentDays = stakeRecord.originalLockDays.toNumber();
-             if (lockPeriod < currentDays) {
-                 setLockPeriod(currentDays);
-             }
-        }
-    }, [stakeRecord, lockPeriod]);
-
-    const fetchStakeRecord = async () => {
-        if (!publicKey) return null;
+    const fetchPositions = async () => {
+        if (!publicKey) return [];
         try {
             const votingProgram = program({ publicKey });
-            const [stakeRecordPDA] = PublicKey.findProgramAddressSync(
-                [Buffer.from(STAKE_RECORD_SEED), publicKey.toBuffer()],
-                programId
-            );
-
-            try {
-                const record = await votingProgram.account.voterStakeRecord.fetch(stakeRecordPDA);
-                setStakeRecord(record);
-                return record;
-            } catch (e) {
-                setStakeRecord(null); 
-                return null;
-            }
+            // Positions sold as NFTs are managed by their new holder
+            const found = (await fetchStakePositions(votingProgram, publicKey)).filter(p => p.held);
+            setPositions(found);
+            return found;
         } catch (e) {
-            console.error("Error fetching stake record:", e);
-            return null;
+            console.error("Error fetching stake positions:", e);
+            return [];
         }
     };
-    
+
     // ... helpers ...
+    // Mirrors `multiplier_for_lock`: 2x at 30 days rising linearly to 5x at 360 days
     const getMultiplier = (days) => {
-        const multiplierMap = { 30: 2, 90: 3, 180: 4, 360: 5 };
-        return multiplierMap[days] || 1;
+        const bonus = 3 * (days - MIN_LOCK_DAYS) / (MAX_LOCK_DAYS - MIN_LOCK_DAYS);
+        return Math.round((2 + bonus) * 100) / 100;
     };
 
+    const totalStaked = positions.reduce((sum, p) => sum + p.account.stakedAmount.toNumber(), 0);
+    const currentPower = votingPower(parseFloat(userBalance) || 0, positions);
+
     const getPreviewDetails = () => {
         const inputAmount = parseFloat(amount) || 0;
         const currentLiquid = parseFloat(userBalance) || 0;
-        const currentStaked = stakeRecord ? parseFloat(stakeRecord.stakedAmount.toString()) : 0; 
-        
         const futureLiquid = Math.max(0, currentLiquid - inputAmount);
-        const futureStaked = currentStaked + inputAmount;
-        const multiplier = getMultiplier(lockPeriod);
 
-        const liquidPower = Math.floor(Math.sqrt(futureLiquid)); // Rust: sqrt() as u64
-        const stakedBase = Math.floor(Math.sqrt(futureStaked));  // Rust: sqrt() as u64
-        const stakedPower = stakedBase * multiplier; // Rust: * multiplier
+        // The deposit opens a new position locked for `lockPeriod`
+        const now = Math.floor(Date.now() / 1000);
+        const newPosition = {
+            account: {
+                stakedAmount: new BN(inputAmount),
+                originalLockDays: new BN(lockPeriod),
+                lockEndTime: new BN(now + lockPeriod),
+                multiplier: new BN(Math.round(getMultiplier(lockPeriod) * 10000)),
+            },
+        };
+        const power = votingPower(futureLiquid, [...positions, newPosition], now);
+        const stakedBase = Math.floor(Math.sqrt(power.staked));
 
         return {
-            total: liquidPower + stakedPower, // Rust: checked_add
-            liquidVP: liquidPower,
-            stakedVP: stakedPower,
+            total: power.total,
+            liquidVP: Math.floor(Math.sqrt(futureLiquid)),
+            stakedVP: power.total - Math.floor(Math.sqrt(futureLiquid)),
             futureLiquid,
-            futureStaked,
-            multiplier,
+            futureStaked: power.staked,
+            multiplier: Math.round(power.multiplier * 100) / 100,
             stakedBaseVP: stakedBase
         };
     };
 
+    const sendAndRefresh = async (transaction, successMessage) => {
+        const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
+        transaction.recentBlockhash = blockhash;
+        transaction.feePayer = publicKey;
+
+        const signature = await sendTransaction(transaction, connection);
+        await connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight });
+
+        // Wait a bit for the RPC to catch up
+        const initial = JSON.stringify(positions.map(p => [p.account.stakedAmount.toString(), p.account.unbondingAmount.toString()]));
+        let retries = 10;
+        while (retries > 0) {
+             await new Promise(r => setTimeout(r, 1000));
+             const updated = await fetchPositions();
+             const next = JSON.stringify(updated.map(p => [p.account.stakedAmount.toString(), p.account.unbondingAmount.toString()]));
+             if (next !== initial) break;
+             retries--;
+        }
+
+        setActionSuccess(successMessage);
+        setTimeout(() => setActionSuccess(''), 2000);
+    };
+
+    // Accounts shared by the instructions that update a position's stake
+    const positionContext = (positionId) => {
+        const [globalAccountPDA] = PublicKey.findProgramAddressSync([Buffer.from(globalStateSeed)], programId);
+        const [rewardPoolPDA] = PublicKey.findProgramAddressSync([Buffer.from(rewardPoolSeed)], programId);
+        const [delegationRecordPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from(delegationRecordSeed), publicKey.toBuffer()],
+            programId
+        );
+        return {
+            globalAccount: globalAccountPDA,
+            stakerProfile: stakerProfilePDA(publicKey),
+            rewardPool: rewardPoolPDA,
+            stakeRecord: stakeRecordPDA(publicKey, positionId),
+            positionTokenAccount: null,
+            delegationRecord: delegationRecordPDA,
+            user: publicKey,
+            systemProgram: SystemProgram.programId,
+        };
+    };
+
     const handleStake = async () => {
         if (!publicKey || !amount || !lockPeriod) return;
         setLoading(true);
@@ -127,81 +174,48 @@ const StakeManager = ({ tokenMintAddress }) => {
 
         try {
             const votingProgram = program({ publicKey });
-            
-            // Re-derive PDAs
-            const [stakeRecordPDA] = PublicKey.findProgramAddressSync(
-                [Buffer.from('stake_record'), publicKey.toBuffer()],
-                programId
-            );
-            const [globalAccountPDA] = PublicKey.findProgramAddressSync(
-                [Buffer.from(globalStateSeed)],
-                programId
-            );
+            const tokenMint = new PublicKey(tokenMintAddress);
             const [vaultPDA] = PublicKey.findProgramAddressSync(
-                [Buffer.from('vault'), new PublicKey(tokenMintAddress).toBuffer()],
+                [Buffer.from(vaultSeed), tokenMint.toBuffer()],
                 programId
             );
-            const userATA = await getAssociatedTokenAddress(new PublicKey(tokenMintAddress), publicKey);
+            const userATA = await getAssociatedTokenAddress(tokenMint, publicKey, false, tokenProgramId);
 
-            const transaction = new Transaction();
+            // Every deposit opens a new position with its own lock
+            let positionId = 0;
+            try {
+                const profile = await votingProgram.account.stakerProfile.fetch(stakerProfilePDA(publicKey));
+                positionId = profile.positionCount.toNumber();
+            } catch (e) { /* First position */ }
+            const context = positionContext(positionId);
 
-            // 1. Check Init (Fetch manually)
-            const stakeAccountInfo = await connection.getAccountInfo(stakeRecordPDA);
-            if (!stakeAccountInfo) {
-                const initIx = await votingProgram.methods
-                    .initializeStake()
-                    .accounts({
-                        stakeRecord: stakeRecordPDA,
-                        user: publicKey,
-                        systemProgram: SystemProgram.programId,
-                    })
-                    .instruction();
-                transaction.add(initIx);
-            }
+            const transaction = new Transaction();
+            const initIx = await votingProgram.methods
+                .initializeStake()
+                .accounts({
+                    stakerProfile: context.stakerProfile,
+                    stakeRecord: context.stakeRecord,
+                    user: publicKey,
+                    systemProgram: SystemProgram.programId,
+                })
+                .instruction();
+            transaction.add(initIx);
 
-            // 2. Deposit Instruction
-            // 2. Deposit Instruction
             const depositIx = await votingProgram.methods
-                .depositTokens(new BN(amount), new BN(lockPeriod))
+                .depositTokens(new BN(positionId), new BN(amount), new BN(lockPeriod))
                 .accounts({
-                    globalAccount: globalAccountPDA,
-                    stakeRecord: stakeRecordPDA,
+                    ...context,
                     vault: vaultPDA,
-                    tokenMint: new PublicKey(tokenMintAddress),
+                    tokenMint,
                     userTokenAccount: userATA,
-                    user: publicKey,
-                    tokenProgram: TOKEN_PROGRAM_ID,
-                    systemProgram: SystemProgram.programId,
+                    tokenProgram: tokenProgramId,
                     rent: SYSVAR_RENT_PUBKEY
                 })
                 .instruction();
             transaction.add(depositIx);
 
-            // Send
-            const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
-            transaction.recentBlockhash = blockhash;
-            transaction.feePayer = publicKey;
-
-            const signature = await sendTransaction(transaction, connection);
-
-            await connection.confirmTransaction({ signature, blockhash, lastValidBlockHeight });
-            
-            // Helper for success effects (polling handled in UI or reload)
-            // Wait a bit for polling to catch up or manually trigger
-            let retries = 10;
-            const initialStaked = stakeRecord ? Number(stakeRecord.stakedAmount) : 0;
-            while (retries > 0) {
-                 await new Promise(r => setTimeout(r, 1000));
-                 const updated = await fetchStakeRecord();
-                 const newStaked = updated ? Number(updated.stakedAmount) : 0;
-                 if (newStaked !== initialStaked) break;
-                 retries--;
-            }
-
-             setLoading(false);
-             setActionSuccess('Tokens Staked!');
-             setTimeout(() => setActionSuccess(''), 2000);
-             setAmount('');
+            await sendAndRefresh(transaction, 'Tokens Staked!');
+            setAmount('');
 
         } catch (err) {
             console.error("Staking Error:", err);
@@ -211,55 +225,67 @@ const StakeManager = ({ tokenMintAddress }) => {
         }
     };
 
-    const handleUnstake = async () => {
+    // Unstaking is two steps: request_unstake starts the unbonding period,
+    // withdraw_unstaked returns the tokens once it has passed.
+    const handleRequestUnstake = async (position) => {
         if (!publicKey) return;
         setLoading(true);
         setError(null);
 
         try {
-            const votingProgram = program({ publicKey, sendTransaction });
-            
-            // Re-derive PDAs (Clean way)
-            const [stakeRecordPDA] = PublicKey.findProgramAddressSync([Buffer.from(STAKE_RECORD_SEED), publicKey.toBuffer()], programId);
+            const votingProgram = program({ publicKey });
+            const [daoConfigPDA] = PublicKey.findProgramAddressSync([Buffer.from(daoConfigSeed)], programId);
+
+            const transaction = await votingProgram.methods
+                .requestUnstake(new BN(position.positionId), position.account.stakedAmount)
+                .accounts({
+                    ...positionContext(position.positionId),
+                    daoConfig: daoConfigPDA,
+                })
+                .transaction();
+
+            await sendAndRefresh(transaction, 'Unbonding Started!');
+
+        } catch (err) {
+            console.error("Unstake Error:", err);
+            setError("Failed to unstake: " + err.message);
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    const handleWithdraw = async (position) => {
+        if (!publicKey) return;
+        setLoading(true);
+        setError(null);
+
+        try {
+            const votingProgram = program({ publicKey });
             const tokenMint = new PublicKey(tokenMintAddress);
-            const [vaultPDA] = PublicKey.findProgramAddressSync([Buffer.from('vault'), tokenMint.toBuffer()], programId);
-            const userATA = await getAssociatedTokenAddress(tokenMint, publicKey);
-            
+            const [globalAccountPDA] = PublicKey.findProgramAddressSync([Buffer.from(globalStateSeed)], programId);
+            const [vaultPDA] = PublicKey.findProgramAddressSync([Buffer.from(vaultSeed), tokenMint.toBuffer()], programId);
+            const userATA = await getAssociatedTokenAddress(tokenMint, publicKey, false, tokenProgramId);
+
             const transaction = await votingProgram.methods
-                .unstakeTokens()
+                .withdrawUnstaked(new BN(position.positionId))
                 .accounts({
-                    stakeRecord: stakeRecordPDA,
+                    globalAccount: globalAccountPDA,
+                    stakeRecord: position.publicKey,
+                    positionTokenAccount: null,
                     vault: vaultPDA,
-                    tokenMint: tokenMint,
                     userTokenAccount: userATA,
+                    tokenMint,
                     user: publicKey,
-                    tokenProgram: TOKEN_PROGRAM_ID,
+                    tokenProgram: tokenProgramId,
                     systemProgram: SystemProgram.programId,
                 })
                 .transaction();
 
-            // Send
-            const signature = await sendTransaction(transaction, connection);
-            await connection.confirmTransaction(signature, 'finalized');
-            
-            // Wait for data update
-            const initialStaked = stakeRecord ? stakeRecord.stakedAmount.toNumber() : 0;
-            let retries = 10;
-            while (retries > 0) {
-                 await new Promise(r => setTimeout(r, 1000));
-                 const updated = await fetchStakeRecord();
-                 const newStaked = updated ? updated.stakedAmount.toNumber() : 0;
-                 if (newStaked !== initialStaked) break;
-                 retries--;
-            }
-
-            setLoading(false);
-            setActionSuccess('Tokens Unstaked!');
-            setTimeout(() => setActionSuccess(''), 2000);
+            await sendAndRefresh(transaction, 'Tokens Withdrawn!');
 
         } catch (err) {
-            console.error("Unstake Error:", err);
-            setError("Failed to unstake: " + err.message);
+            console.error("Withdraw Error:", err);
+            setError("Failed to withdraw: " + err.message);
         } finally {
             setLoading(false);
         }
@@ -333,19 +359,14 @@ const StakeManager = ({ tokenMintAddress }) => {
                         <label className="block text-gray-400 text-sm mb-2">Lock Duration</label>
                         <div className="grid grid-cols-4 gap-2">
                             {[30, 90, 180, 360].map((days) => {
-                                const currentDays = stakeRecord ? stakeRecord.originalLockDays.toNumber() : 0;
-                                const isInvalid = currentDays > days;
                                 return (
                                 <button
                                     key={days}
-                                    onClick={() => !isInvalid && setLockPeriod(days)}
-                                    disabled={isInvalid}
+                                    onClick={() => setLockPeriod(days)}
                                     className={`p-3 rounded-lg border text-sm font-medium transition-all ${
                                         lockPeriod === days 
                                         ? 'bg-pulsar-primary/20 border-pulsar-primary text-white'
-                                        : isInvalid
-                                            ? 'bg-black/10 border-white/5 text-gray-600 cursor-not-allowed opacity-50'
-                                            : 'bg-black/20 border-white/5 text-gray-400 hover:border-white/20'
+                                        : 'bg-black/20 border-white/5 text-gray-400 hover:border-white/20'
                                     }`}
                                 >
                                     {days}s
@@ -372,10 +393,7 @@ const StakeManager = ({ tokenMintAddress }) => {
                         {/* COMPARISON HEADER */}
                         <div className="flex items-baseline gap-3 mb-4 font-mono">
                              <span className="text-gray-500 line-through text-lg">
-                                 {stakeRecord 
-                                  ? (Math.floor(Math.sqrt(parseFloat(userBalance) || 0)) + Math.floor(Math.sqrt(stakeRecord.stakedAmount.toString())) * stakeRecord.multiplier.toNumber()).toLocaleString()
-                                  : Math.floor(Math.sqrt(parseFloat(userBalance) || 0)).toLocaleString()
-                                 }
+                                 {currentPower.total.toLocaleString()}
                              </span>
                              <span className="text-gray-500">→</span>
                              <span className="text-3xl font-bold text-white text-glow">
@@ -411,79 +429,99 @@ const StakeManager = ({ tokenMintAddress }) => {
                 </div>
 
                 {/* CURRENT STATUS */}
-                <div className="bg-gradient-to-br from-pulsar-secondary/10 to-transparent rounded-xl border border-pulsar-secondary/20 p-6 flex flex-col justify-between">
-                    <div>
-                        <h3 className="text-white font-bold mb-4 flex items-center gap-2">
-                            The Vault
-                        </h3>
-                        
-                        <div className="space-y-4">
-                            <div className="flex justify-between items-start">
-                                <span className="text-gray-400 text-sm">Staked Balance</span>
-                                <div className="text-right">
-                                    <div className="text-xl font-mono text-white">
-                                        {stakeRecord ? (stakeRecord.stakedAmount.toString()) : "0"}
-                                    </div>
-                                    <div className="text-xs text-pulsar-secondary">Tokens Locked</div>
+                <div className="bg-gradient-to-br from-pulsar-secondary/10 to-transparent rounded-xl border border-pulsar-secondary/20 p-6 flex flex-col">
+                    <h3 className="text-white font-bold mb-4 flex items-center gap-2">
+                        The Vault
+                    </h3>
+
+                    <div className="space-y-4">
+                        <div className="flex justify-between items-start">
+                            <span className="text-gray-400 text-sm">Staked Balance</span>
+                            <div className="text-right">
+                                <div className="text-xl font-mono text-white">
+                                    {totalStaked.toLocaleString()}
                                 </div>
+                                <div className="text-xs text-pulsar-secondary">Tokens Locked</div>
                             </div>
+                        </div>
 
-                            <div className="h-px bg-white/10" />
-
-                            <div className="flex justify-between items-start">
-                                <span className="text-gray-400 text-sm">Current Voting Power</span>
-                                <div className="text-right">
-                                    <div className="text-2xl font-bold text-white text-glow">
-                                         {stakeRecord 
-                                         ? (Math.floor(Math.sqrt(parseFloat(userBalance) || 0)) + Math.floor(Math.sqrt(stakeRecord.stakedAmount.toString())) * stakeRecord.multiplier.toNumber()).toLocaleString()
-                                         : Math.floor(Math.sqrt(parseFloat(userBalance) || 0)).toLocaleString()
-                                         }
-                                    </div>
-                                    <div className="text-xs text-emerald-400 flex items-center justify-end gap-1">
-                                        {stakeRecord ? `${stakeRecord.multiplier.toString()}x Boost Active` : "No Active Boost"}
-                                    </div>
-                                </div>
-                            </div>
+                        <div className="h-px bg-white/10" />
 
-                            <div className="h-px bg-white/10" />
-
-                             <div className="flex justify-between items-start">
-                                <span className="text-gray-400 text-sm">Unlock Date</span>
-                                <div className="text-right">
-                                    <div className="text-sm text-white">
-                                        {stakeRecord && stakeRecord.lockEndTime.toNumber() > 0
-                                            ? new Date(stakeRecord.lockEndTime.toNumber() * 1000).toLocaleDateString() 
-                                            : "No Lock"}
-                                    </div>
-                                    <div className="text-xs text-gray-500">
-                                         {stakeRecord && stakeRecord.lockEndTime.toNumber() * 1000 > Date.now() 
-                                         ? `${Math.ceil((stakeRecord.lockEndTime.toNumber() * 1000 - Date.now()) / 1000)}s left` 
-                                         : "Unlocked"}
-                                    </div>
+                        <div className="flex justify-between items-start">
+                            <span className="text-gray-400 text-sm">Current Voting Power</span>
+                            <div className="text-right">
+                                <div className="text-2xl font-bold text-white text-glow">
+                                     {currentPower.total.toLocaleString()}
+                                </div>
+                                <div className="text-xs text-emerald-400 flex items-center justify-end gap-1">
+                                    {totalStaked > 0 ? `${Math.round(currentPower.multiplier * 100) / 100}x Boost Active` : "No Active Boost"}
                                 </div>
                             </div>
                         </div>
-                    </div>
 
-                    <button 
-                        onClick={handleUnstake}
-                        disabled={loading || !stakeRecord || stakeRecord.stakedAmount.toNumber() === 0 || stakeRecord.lockEndTime.toNumber() * 1000 > Date.now()}
-                        className="w-full mt-6 border border-white/20 hover:bg-white/5 disabled:opacity-30 disabled:cursor-not-allowed text-white font-medium py-3 rounded-xl transition-all flex items-center justify-center gap-2"
-                    >
-                        {loading ? (
-                             <><Loader2 className="animate-spin" /> Processing...</>
-                        ) : (
-                            <>
-                                {stakeRecord && stakeRecord.lockEndTime.toNumber() * 1000 > Date.now() 
-                                ? <Lock size={16} /> 
-                                : <Unlock size={16} />}
-                                {stakeRecord && stakeRecord.lockEndTime.toNumber() * 1000 > Date.now() 
-                                 ? "Tokens Locked" 
-                                 : "Unstake & Withdraw"}
-                            </>
-                        )}
-                    </button>
-                    
+                        <div className="h-px bg-white/10" />
+
+                        {/* POSITIONS */}
+                        <div className="space-y-3">
+                            {positions.filter(p => p.account.stakedAmount.toNumber() > 0 || p.account.unbondingAmount.toNumber() > 0).length === 0 && (
+                                <p className="text-sm text-gray-500">No stake positions yet.</p>
+                            )}
+                            {positions
+                                .filter(p => p.account.stakedAmount.toNumber() > 0 || p.account.unbondingAmount.toNumber() > 0)
+                                .map((position) => {
+                                    const record = position.account;
+                                    const now = Date.now();
+                                    const locked = record.lockEndTime.toNumber() * 1000 > now;
+                                    const unbonding = record.unbondingAmount.toNumber() > 0;
+                                    const unbonded = unbonding && record.unbondingEndTime.toNumber() * 1000 <= now;
+                                    return (
+                                        <div key={position.publicKey.toString()} className="bg-black/20 rounded-lg p-3 border border-white/5">
+                                            <div className="flex justify-between items-center text-sm">
+                                                <span className="text-gray-400">Position #{position.positionId}</span>
+                                                <span className="font-mono text-white">{record.stakedAmount.toString()}</span>
+                                            </div>
+                                            <div className="flex justify-between items-center text-xs text-gray-500 mt-1">
+                                                <span>{(record.multiplier.toNumber() / 10000).toFixed(2)}x lock</span>
+                                                <span>
+                                                    {locked
+                                                        ? `${Math.ceil((record.lockEndTime.toNumber() * 1000 - now) / 1000)}s left`
+                                                        : "Unlocked"}
+                                                </span>
+                                            </div>
+                                            {unbonding && (
+                                                <div className="flex justify-between items-center text-xs text-amber-400 mt-1">
+                                                    <span className="flex items-center gap-1"><Clock size={12} /> Unbonding {record.unbondingAmount.toString()}</span>
+                                                    <span>
+                                                        {unbonded
+                                                            ? "Ready"
+                                                            : `${Math.ceil((record.unbondingEndTime.toNumber() * 1000 - now) / 1000)}s left`}
+                                                    </span>
+                                                </div>
+                                            )}
+                                            <div className="flex gap-2 mt-3">
+                                                <button
+                                                    onClick={() => handleRequestUnstake(position)}
+                                                    disabled={loading || locked || record.stakedAmount.toNumber() === 0}
+                                                    className="flex-1 border border-white/20 hover:bg-white/5 disabled:opacity-30 disabled:cursor-not-allowed text-white text-xs font-medium py-2 rounded-lg transition-all flex items-center justify-center gap-1"
+                                                >
+                                                    {locked ? <Lock size={12} /> : <Unlock size={12} />}
+                                                    {locked ? "Tokens Locked" : "Request Unstake"}
+                                                </button>
+                                                {unbonding && (
+                                                    <button
+                                                        onClick={() => handleWithdraw(position)}
+                                                        disabled={loading || !unbonded}
+                                                        className="flex-1 bg-pulsar-primary/20 hover:bg-pulsar-primary/30 disabled:opacity-30 disabled:cursor-not-allowed text-pulsar-primary text-xs font-medium py-2 rounded-lg transition-all"
+                                                    >
+                                                        Withdraw
+                                                    </button>
+                                                )}
+                                            </div>
+                                        </div>
+                                    );
+                                })}
+                        </div>
+                    </div>
                 </div>
             </div>
         </div>
@@ -15,7 +15,7 @@ import {
 } from '@solana/spl-token';
 import { PublicKey, Keypair, SystemProgram, Transaction } from '@solana/web3.js';
 import { BN } from 'bn.js';
-import { program, globalAccountPDAAddress, programId, globalStateSeed, delegateProfileSeed, delegationRecordSeed } from '../config';
+import { program, globalAccountPDAAddress, programId, globalStateSeed, delegateProfileSeed, delegationRecordSeed, councilSeed } from '../config';
 import { Activity, Coins, Shield, AlertTriangle, Users, Trash2, Loader2 } from 'lucide-react';
 
 const TokenManager = () => {
@@ -102,8 +102,10 @@ const TokenManager = () => {
            const records = await daoProgram.account.delegationRecord.all();
            const counts = {};
            records.forEach(r => {
-                const target = r.account.delegateTarget.toString();
-                counts[target] = (counts[target] || 0) + 1;
+                r.account.shares.forEach(share => {
+                    const target = share.delegate.toString();
+                    counts[target] = (counts[target] || 0) + 1;
+                });
            });
 
            setDelegates(activeProfiles.map(p => ({
@@ -313,21 +315,26 @@ const TokenManager = () => {
                programId
            );
            
+           let isDelegating = false;
            try {
-               await daoProgram.account.delegationRecord.fetch(delegationRecordPDA);
-               // If successful, they have a record!
-               setMessage("Failed: This user is already delegating to someone. They must revoke first.");
-               setDelegateActionLoading(false);
-               return; 
+               const record = await daoProgram.account.delegationRecord.fetch(delegationRecordPDA);
+               // A record voided by a suspension is left with no shares
+               isDelegating = record.shares.length > 0;
            } catch (e) {
                // Expected: Account not found error means they are free
            }
+           if (isDelegating) {
+               setMessage("Failed: This user is already delegating to someone. They must revoke first.");
+               setDelegateActionLoading(false);
+               return;
+           }
 
            const [profilePDA] = PublicKey.findProgramAddressSync(
                [Buffer.from(delegateProfileSeed), targetPubkey.toBuffer()],
                programId
            );
            const [globalAccountPDA] = PublicKey.findProgramAddressSync([Buffer.from(globalStateSeed)], programId);
+           const [councilPDA] = PublicKey.findProgramAddressSync([Buffer.from(councilSeed)], programId);
 
            await daoProgram.methods.registerDelegate()
              .accounts({
@@ -335,6 +342,8 @@ const TokenManager = () => {
                  delegateProfile: profilePDA,
                  targetUser: targetPubkey,
                  admin: publicKey,
+                 userDelegationRecord: delegationRecordPDA,
+                 council: councilPDA,
                  systemProgram: SystemProgram.programId
              })
              .rpc();
@@ -370,6 +379,42 @@ const TokenManager = () => {
            );
            
            const [globalAccountPDA] = PublicKey.findProgramAddressSync([Buffer.from(globalStateSeed)], programId);
+           const [councilPDA] = PublicKey.findProgramAddressSync([Buffer.from(councilSeed)], programId);
+
+           // Only a suspended delegate can be removed. Suspending voids every
+           // delegation record naming them: each record is followed by the
+           // profiles of its other delegates.
+           const profile = await daoProgram.account.delegateProfile.fetch(profilePDA);
+           const preInstructions = [];
+           if (profile.isActive) {
+               const records = await daoProgram.account.delegationRecord.all();
+               const voidAccounts = records
+                   .filter(r => r.account.shares.some(share => share.delegate.equals(targetPubkey)))
+                   .flatMap(r => [
+                       { pubkey: r.publicKey, isWritable: true, isSigner: false },
+                       ...r.account.shares
+                           .filter(share => !share.delegate.equals(targetPubkey))
+                           .map(share => ({
+                               pubkey: PublicKey.findProgramAddressSync(
+                                   [Buffer.from(delegateProfileSeed), share.delegate.toBuffer()],
+                                   programId
+                               )[0],
+                               isWritable: true,
+                               isSigner: false,
+                           })),
+                   ]);
+
+               preInstructions.push(await daoProgram.methods.suspendDelegate("Removed by admin")
+                 .accounts({
+                     globalAccount: globalAccountPDA,
+                     delegateProfile: profilePDA,
+                     targetUser: targetPubkey,
+                     council: councilPDA,
+                     admin: publicKey,
+                 })
+                 .remainingAccounts(voidAccounts)
+                 .instruction());
+           }
 
            await daoProgram.methods.removeDelegate()
              .accounts({
@@ -378,6 +423,7 @@ const TokenManager = () => {
                  targetUser: targetPubkey,
                  admin: publicKey,
              })
+             .preInstructions(preInstructions)
              .rpc();
             
            setMessage(`Successfully removed delegate: ${targetPubkey.toString()}`);
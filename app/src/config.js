@@ -2,7 +2,8 @@
 // Updated IDL verify
 
 import { Connection, PublicKey, Keypair } from '@solana/web3.js';
-import { AnchorProvider, Program } from "@coral-xyz/anchor";
+import { AnchorProvider, Program, BN } from "@coral-xyz/anchor";
+import { getAssociatedTokenAddressSync } from '@solana/spl-token';
 
 // Solana network endpoint (Devnet in this case)
 // const localhost = 'http://127.0.0.1:8899';
@@ -25,6 +26,17 @@ export const delegationRecordSeed = 'delegation_record';
 export const proposalEscrowSeed = 'proposal_escrow';
 export const userStatsSeed = 'user_stats_v2';
 export const badgeMintSeed = 'badge';
+export const stakerProfileSeed = 'staker_profile';
+export const stakeRecordSeed = 'stake_record';
+export const vaultSeed = 'vault';
+export const rewardPoolSeed = 'reward_pool';
+export const rewardVaultSeed = 'reward_vault';
+export const daoConfigSeed = 'dao_config';
+export const councilSeed = 'council';
+
+// The devnet program is built with `accelerated-time`: a lock "day" lasts one second.
+export const secondsPerDay = 1;
+const MULTIPLIER_BPS = 10000;
 
 // Helper to create the AnchorProvider instance
 const getProvider = (wallet) => {
@@ -52,4 +64,90 @@ export const program = (wallet) => {
 export const [globalAccountPDAAddress] = PublicKey.findProgramAddressSync(
     [Buffer.from(globalStateSeed)], // Seed for global account
     programId // Use programId from config.js
-);
\ No newline at end of file
+);
+
+export const stakerProfilePDA = (owner) => PublicKey.findProgramAddressSync(
+    [Buffer.from(stakerProfileSeed), owner.toBuffer()],
+    programId
+)[0];
+
+export const stakeRecordPDA = (owner, positionId) => PublicKey.findProgramAddressSync(
+    [Buffer.from(stakeRecordSeed), owner.toBuffer(), new BN(positionId).toArrayLike(Buffer, 'le', 8)],
+    programId
+)[0];
+
+// Every stake position `owner` opened, in position id order
+export const fetchStakePositions = async (votingProgram, owner) => {
+    let positionCount = 0;
+    try {
+        const profile = await votingProgram.account.stakerProfile.fetch(stakerProfilePDA(owner));
+        positionCount = profile.positionCount.toNumber();
+    } catch (e) {
+        return [];
+    }
+
+    const keys = [...Array(positionCount).keys()].map((id) => stakeRecordPDA(owner, id));
+    const records = await votingProgram.account.voterStakeRecord.fetchMultiple(keys);
+    const positions = keys
+        .map((publicKey, id) => ({ publicKey, positionId: id, account: records[id], held: true }))
+        .filter((position) => position.account);
+
+    // A tokenized position belongs to whoever holds its NFT
+    for (const position of positions.filter(isTokenized)) {
+        const nftAccount = getAssociatedTokenAddressSync(position.account.positionMint, owner);
+        try {
+            const balance = await votingProgram.provider.connection.getTokenAccountBalance(nftAccount);
+            position.held = balance.value.amount === '1';
+        } catch (e) {
+            position.held = false;
+        }
+    }
+    return positions;
+};
+
+const isTokenized = (position) => !position.account.positionMint.equals(PublicKey.default);
+
+// Remaining accounts for instructions that read stake positions: each
+// position, writable, followed by `holder`'s token account for its NFT
+// when the position is tokenized and still theirs. Sold positions may only
+// be passed where every opened position is required, such as delegation.
+export const positionAccounts = (positions, holder) => positions.flatMap((position) => {
+    const metas = [{ pubkey: position.publicKey, isWritable: true, isSigner: false }];
+    if (isTokenized(position) && position.held) {
+        metas.push({
+            pubkey: getAssociatedTokenAddressSync(position.account.positionMint, holder),
+            isWritable: false,
+            isSigner: false,
+        });
+    }
+    return metas;
+});
+
+// Mirrors `voting_multiplier_bps`: the lock bonus decays to 1x at lock end
+const positionMultiplierBps = (record, at) => {
+    const lockDuration = record.originalLockDays.toNumber() * secondsPerDay;
+    const multiplier = record.multiplier.toNumber();
+    if (lockDuration <= 0 || multiplier <= MULTIPLIER_BPS) return MULTIPLIER_BPS;
+
+    const remaining = Math.min(Math.max(record.lockEndTime.toNumber() - at, 0), lockDuration);
+    return MULTIPLIER_BPS + Math.floor((multiplier - MULTIPLIER_BPS) * remaining / lockDuration);
+};
+
+// Mirrors `calculate_voting_power`: sqrt(liquid) + sqrt(staked) x stake-weighted multiplier
+export const votingPower = (liquidAmount, positions, at = Math.floor(Date.now() / 1000)) => {
+    let staked = 0;
+    let weightedMultiplier = 0;
+    positions.filter((position) => position.held).forEach(({ account }) => {
+        const amount = account.stakedAmount.toNumber();
+        staked += amount;
+        weightedMultiplier += amount * positionMultiplierBps(account, at);
+    });
+
+    const multiplierBps = staked > 0 ? Math.floor(weightedMultiplier / staked) : MULTIPLIER_BPS;
+    const stakedPower = Math.floor(Math.floor(Math.sqrt(staked)) * multiplierBps / MULTIPLIER_BPS);
+    return {
+        total: Math.floor(Math.sqrt(liquidAmount)) + stakedPower,
+        staked,
+        multiplier: multiplierBps / MULTIPLIER_BPS,
+    };
+};
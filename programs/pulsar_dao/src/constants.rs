@@ -0,0 +1,15 @@
+////////////////////////////////////////////////////////////////
+//                        TIME UNITS
+////////////////////////////////////////////////////////////////
+
+/// Length of a "day" for lock durations, faucet cooldowns and proposal
+/// voting windows. Build with `--features accelerated-time` for localnet
+/// tests, where a day lasts one second.
+#[cfg(not(feature = "accelerated-time"))]
+pub const SECONDS_PER_DAY: i64 = 86_400;
+#[cfg(feature = "accelerated-time")]
+pub const SECONDS_PER_DAY: i64 = 1;
+
+pub fn days_to_seconds(days: i64) -> i64 {
+    days.checked_mul(SECONDS_PER_DAY).unwrap()
+}
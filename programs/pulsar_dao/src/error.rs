@@ -4,6 +4,8 @@ use anchor_lang::prelude::*;
 //                        ERROR CODES
 ////////////////////////////////////////////////////////////////
 
+// New variants go at the end of the enum: error numbers are part of the
+// client interface and must not shift.
 #[error_code]
 pub enum ErrorCode {
     // Proposal & Voting Errors
@@ -75,4 +77,98 @@ pub enum ErrorCode {
     InsufficientScore,
     #[msg("Badge already claimed.")]
     AlreadyClaimed,
+
+    // Batch & Relayed Voting Errors
+    #[msg("Batch must contain between 1 and 10 votes.")]
+    InvalidBatchSize,
+    #[msg("Batch accounts do not match the submitted entries.")]
+    InvalidBatchAccounts,
+    #[msg("Missing or invalid ed25519 vote signature.")]
+    InvalidVoteSignature,
+    #[msg("Signed vote has expired.")]
+    SignatureExpired,
+    #[msg("Vote nonce has already been used or is out of order.")]
+    InvalidNonce,
+
+    // Stake Position Errors
+    #[msg("Stake position does not belong to the voter or was passed twice.")]
+    InvalidStakePosition,
+    #[msg("Stake position has no tokens.")]
+    EmptyStakePosition,
+    #[msg("Unstake amount exceeds the staked balance.")]
+    InsufficientStakedBalance,
+    #[msg("Unstaked tokens are still unbonding.")]
+    UnbondingInProgress,
+    #[msg("Invalid configuration value.")]
+    InvalidConfig,
+    #[msg("Deadline must be in the future and within the maximum voting period.")]
+    InvalidDeadline,
+    #[msg("No staking rewards available to claim.")]
+    NoRewardsToClaim,
+    #[msg("This stake position is already tokenized.")]
+    PositionAlreadyTokenized,
+    #[msg("The circuit breaker must be tripped first.")]
+    CircuitBreakerNotTripped,
+    #[msg("Emergency exit is not active.")]
+    EmergencyExitNotActive,
+
+    // Delegate Profile & Split Delegation Errors
+    #[msg("Delegation shares must name distinct delegates and sum to 100%.")]
+    InvalidDelegationShares,
+    #[msg("Delegation record does not delegate to this delegate.")]
+    InvalidDelegationRecord,
+    #[msg("Delegate metadata exceeds the allowed lengths.")]
+    InvalidDelegateMetadata,
+    #[msg("Participation for this proposal is already recorded.")]
+    ParticipationRecorded,
+    #[msg("Proposal was created before the delegate registered.")]
+    ProposalPredatesDelegate,
+    #[msg("Revoke the existing delegation first.")]
+    DelegationActive,
+    #[msg("Delegation expiry must be in the future and scope must cover at least one proposal type.")]
+    InvalidDelegationTerms,
+    #[msg("Delegation has expired.")]
+    DelegationExpired,
+    #[msg("Proposal type is outside the delegation's scope.")]
+    ProposalOutOfScope,
+    #[msg("Commission exceeds the configured maximum.")]
+    CommissionTooHigh,
+    #[msg("Delegate must be suspended first.")]
+    DelegateNotSuspended,
+    #[msg("No proxy vote from this delegate on the voter record.")]
+    NoProxyVote,
+
+    // Council Errors
+    #[msg("Proposal was vetoed by the council.")]
+    ProposalVetoed,
+    #[msg("Council needs 1 to 7 seats and a term of 1 to 365 days.")]
+    InvalidCouncilConfig,
+    #[msg("Signer does not hold a council seat.")]
+    NotCouncilMember,
+    #[msg("No council seat is open for election.")]
+    NoCouncilVacancy,
+    #[msg("A council election is already open.")]
+    ElectionOpen,
+    #[msg("Council election voting has ended.")]
+    ElectionClosed,
+    #[msg("Council election has already been finalized.")]
+    ElectionFinalized,
+    #[msg("Candidate is not standing, already standing, or the ballot is full.")]
+    InvalidCandidate,
+
+    // Delegate Removal Errors
+    #[msg("Delegation records still name this delegate. Pass all of them to void.")]
+    DelegatorsRemain,
+
+    // Delegation Resync Errors
+    #[msg("Every stake position the delegator opened must be passed, in order.")]
+    MissingStakePosition,
+
+    // Position Vote Errors
+    #[msg("Stake position is counted in another wallet's live votes.")]
+    PositionVoteReserved,
+
+    // Emergency Proposal Errors
+    #[msg("Only proposals flagged as emergencies at creation can be fast-tracked.")]
+    NotEmergencyProposal,
 }
@@ -1,9 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, Mint, TokenAccount, self};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::error::ErrorCode;
 
 pub const GLOBAL_ACCOUNT_SEED: &[u8] = b"global_account";
+pub const DAO_CONFIG_SEED: &[u8] = b"dao_config";
+pub const TREASURY_VAULT_SEED: &[u8] = b"treasury_vault";
+
+pub const MAX_BPS: u16 = 10_000;
+pub const MAX_UNBONDING_PERIOD_DAYS: i64 = 30;
+pub const MAX_COMMISSION_NOTICE_DAYS: i64 = 30;
+
+// Config used until `initialize_config` creates the DaoConfig account
+pub const DEFAULT_EARLY_UNSTAKE_PENALTY_BPS: u16 = 5_000;
+pub const DEFAULT_BURN_EARLY_UNSTAKE_PENALTY: bool = true; // No treasury vault before `initialize_config`
+pub const DEFAULT_UNBONDING_PERIOD_DAYS: i64 = 7;
+pub const DEFAULT_MAX_DELEGATE_COMMISSION_BPS: u16 = 0;
+pub const DEFAULT_COMMISSION_NOTICE_DAYS: i64 = 7;
 
 ////////////////////////////////////////////////////////////////
 //                       ADMIN CONTEXTS
@@ -15,14 +28,15 @@ pub struct Initialize<'info> {
         init,
         payer = user,
         // Space: 8 (discriminator) + 32 (admin) + 32 (token_mint) + 8 (proposal_count) + 1 (system_enabled)
-        space = 8 + 32 + 32 + 8 + 1,
+        //        + 8 (total_staked) + 8 (total_weighted_stake) + 8 (staker_count) + 1 (emergency_exit_enabled)
+        space = 8 + 32 + 32 + 8 + 1 + 8 + 8 + 8 + 1,
         seeds = [GLOBAL_ACCOUNT_SEED],
         bump
     )]
     pub global_account: Account<'info, GlobalAccount>,
     #[account(mut)]
     pub user: Signer<'info>,
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     pub system_program: Program<'info, System>,
 }
 
@@ -50,14 +64,93 @@ pub struct AdminMint<'info> {
         mut,
         constraint = token_mint.mint_authority.unwrap() == global_account.key()
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(mut)]
-    pub target_token_account: Account<'info, TokenAccount>,
+    pub target_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub admin: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ToggleEmergencyExit<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        // Space: 8 (discriminator) + 2 (early_unstake_penalty_bps) + 1 (burn_early_unstake_penalty) + 8 (unbonding_period_days)
+        //        + 2 (max_delegate_commission_bps) + 8 (commission_notice_days)
+        space = 8 + 2 + 1 + 8 + 2 + 8,
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [TREASURY_VAULT_SEED, token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = treasury_vault,
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == global_account.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        mut,
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: Account<'info, DaoConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub early_unstake_penalty_bps: u16,
+    pub burn_early_unstake_penalty: bool,
+    pub unbonding_period_days: i64,
+    pub max_delegate_commission_bps: u16,
+    pub commission_notice_days: i64,
 }
 
 ////////////////////////////////////////////////////////////////
@@ -70,6 +163,10 @@ pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
     global_account.token_mint = ctx.accounts.token_mint.key();
     global_account.proposal_count = 0;
     global_account.system_enabled = true;
+    global_account.total_staked = 0;
+    global_account.total_weighted_stake = 0;
+    global_account.staker_count = 0;
+    global_account.emergency_exit_enabled = false;
     Ok(())
 }
 
@@ -83,13 +180,29 @@ pub fn toggle_circuit_breaker(ctx: Context<ToggleCircuitBreaker>) -> Result<()>
     Ok(())
 }
 
+/// Lets stakers pull their tokens out past any lock. Can only be switched
+/// on while the circuit breaker is tripped.
+pub fn toggle_emergency_exit(ctx: Context<ToggleEmergencyExit>) -> Result<()> {
+    let global_account = &mut ctx.accounts.global_account;
+    require!(
+        global_account.admin == ctx.accounts.user.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(
+        global_account.emergency_exit_enabled || !global_account.system_enabled,
+        ErrorCode::CircuitBreakerNotTripped
+    );
+    global_account.emergency_exit_enabled = !global_account.emergency_exit_enabled;
+    Ok(())
+}
+
 pub fn admin_mint(ctx: Context<AdminMint>, amount: u64) -> Result<()> {
     let amount_with_decimals = amount.checked_mul(10u64.pow(ctx.accounts.token_mint.decimals as u32)).unwrap();
 
-    token::mint_to(
+    token_interface::mint_to(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            token::MintTo {
+            token_interface::MintTo {
                 mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.target_token_account.to_account_info(),
                 authority: ctx.accounts.global_account.to_account_info(),
@@ -100,3 +213,56 @@ pub fn admin_mint(ctx: Context<AdminMint>, amount: u64) -> Result<()> {
     )?;
     Ok(())
 }
+
+/// Create the DAO config and the treasury vault that collects penalties.
+pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+    require!(
+        ctx.accounts.global_account.admin == ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+    apply_config(&mut ctx.accounts.dao_config, params)
+}
+
+pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+    require!(
+        ctx.accounts.global_account.admin == ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+    apply_config(&mut ctx.accounts.dao_config, params)
+}
+
+/// Reads the DAO config, falling back to the `DEFAULT_*` values while
+/// `initialize_config` has not run, so deployments predating the config
+/// keep working.
+pub fn load_dao_config(dao_config_info: &AccountInfo, program_id: &Pubkey) -> Result<DaoConfig> {
+    if dao_config_info.data_is_empty() || dao_config_info.owner != program_id {
+        return Ok(DaoConfig {
+            early_unstake_penalty_bps: DEFAULT_EARLY_UNSTAKE_PENALTY_BPS,
+            burn_early_unstake_penalty: DEFAULT_BURN_EARLY_UNSTAKE_PENALTY,
+            unbonding_period_days: DEFAULT_UNBONDING_PERIOD_DAYS,
+            max_delegate_commission_bps: DEFAULT_MAX_DELEGATE_COMMISSION_BPS,
+            commission_notice_days: DEFAULT_COMMISSION_NOTICE_DAYS,
+        });
+    }
+    DaoConfig::try_deserialize(&mut &dao_config_info.try_borrow_data()?[..])
+}
+
+fn apply_config(dao_config: &mut DaoConfig, params: ConfigParams) -> Result<()> {
+    require!(params.early_unstake_penalty_bps <= MAX_BPS, ErrorCode::InvalidConfig);
+    require!(
+        (0..=MAX_UNBONDING_PERIOD_DAYS).contains(&params.unbonding_period_days),
+        ErrorCode::InvalidConfig
+    );
+    require!(params.max_delegate_commission_bps <= MAX_BPS, ErrorCode::InvalidConfig);
+    require!(
+        (0..=MAX_COMMISSION_NOTICE_DAYS).contains(&params.commission_notice_days),
+        ErrorCode::InvalidConfig
+    );
+
+    dao_config.early_unstake_penalty_bps = params.early_unstake_penalty_bps;
+    dao_config.burn_early_unstake_penalty = params.burn_early_unstake_penalty;
+    dao_config.unbonding_period_days = params.unbonding_period_days;
+    dao_config.max_delegate_commission_bps = params.max_delegate_commission_bps;
+    dao_config.commission_notice_days = params.commission_notice_days;
+    Ok(())
+}
@@ -0,0 +1,435 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::constants::days_to_seconds;
+use crate::instructions::admin::GLOBAL_ACCOUNT_SEED;
+use crate::instructions::delegation::DELEGATE_PROFILE_SEED;
+use crate::instructions::proposal::require_valid_deadline;
+use crate::instructions::staking::{load_stake_positions, reserve_stake_position};
+use crate::instructions::voting::calculate_voting_power;
+
+pub const COUNCIL_SEED: &[u8] = b"council";
+pub const COUNCIL_ELECTION_SEED: &[u8] = b"council_election";
+pub const COUNCIL_BALLOT_SEED: &[u8] = b"council_ballot";
+pub const COUNCIL_MOTION_SEED: &[u8] = b"council_motion";
+
+pub const MAX_COUNCIL_SEATS: usize = 7;
+pub const MAX_COUNCIL_TERM_DAYS: i64 = 365;
+pub const MAX_ELECTION_CANDIDATES: usize = 10;
+pub const FAST_TRACK_VOTING_DAYS: i64 = 1;
+
+// Space: 8 (discriminator) + 4 (seats len) + MAX_COUNCIL_SEATS * (32 (member) + 8 (term_end))
+//        + 8 (term_days) + 8 (election_count) + 1 (election_open)
+pub const COUNCIL_SPACE: usize = 8 + 4 + MAX_COUNCIL_SEATS * (32 + 8) + 8 + 8 + 1;
+
+// Space: 8 (discriminator) + 8 (number) + 1 (seats_up) + 8 (deadline)
+//        + 4 (candidates len) + MAX_ELECTION_CANDIDATES * (32 (candidate) + 8 (votes)) + 1 (finalized)
+pub const COUNCIL_ELECTION_SPACE: usize = 8 + 8 + 1 + 8 + 4 + MAX_ELECTION_CANDIDATES * (32 + 8) + 1;
+
+// Space: 8 (discriminator) + 32 (election) + 32 (voter) + 32 (candidate) + 8 (voting_power)
+pub const COUNCIL_BALLOT_SPACE: usize = 8 + 32 + 32 + 32 + 8;
+
+// Space: 8 (discriminator) + 32 (proposal)
+//        + 4 + MAX_COUNCIL_SEATS * 32 (veto_approvals) + 4 + MAX_COUNCIL_SEATS * 32 (fast_track_approvals)
+pub const COUNCIL_MOTION_SPACE: usize = 8 + 32 + 4 + MAX_COUNCIL_SEATS * 32 + 4 + MAX_COUNCIL_SEATS * 32;
+
+////////////////////////////////////////////////////////////////
+//                     COUNCIL CONTEXTS
+////////////////////////////////////////////////////////////////
+
+#[derive(Accounts)]
+pub struct InitializeCouncil<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump,
+        constraint = global_account.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = COUNCIL_SPACE,
+        seeds = [COUNCIL_SEED],
+        bump
+    )]
+    pub council: Account<'info, Council>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Anyone can open an election once a seat is vacant or its term ends
+/// before the election's deadline. Only one election runs at a time.
+#[derive(Accounts)]
+pub struct OpenCouncilElection<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        mut,
+        seeds = [COUNCIL_SEED],
+        bump
+    )]
+    pub council: Account<'info, Council>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = COUNCIL_ELECTION_SPACE,
+        seeds = [COUNCIL_ELECTION_SEED, (council.election_count + 1).to_le_bytes().as_ref()],
+        bump
+    )]
+    pub election: Account<'info, CouncilElection>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Only active delegates can stand.
+#[derive(Accounts)]
+pub struct StandForCouncil<'info> {
+    #[account(mut)]
+    pub election: Account<'info, CouncilElection>,
+
+    #[account(
+        seeds = [DELEGATE_PROFILE_SEED, candidate.key().as_ref()],
+        bump,
+        constraint = candidate_profile.is_active @ ErrorCode::InvalidDelegate
+    )]
+    pub candidate_profile: Account<'info, DelegateProfile>,
+
+    pub candidate: Signer<'info>,
+}
+
+/// The voter's stake positions are passed through `remaining_accounts`,
+/// writable. Only those locked past the election deadline count.
+#[derive(Accounts)]
+pub struct VoteCouncilElection<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(mut)]
+    pub election: Account<'info, CouncilElection>,
+
+    #[account(
+        init,
+        payer = user,
+        space = COUNCIL_BALLOT_SPACE,
+        seeds = [COUNCIL_BALLOT_SEED, election.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub ballot: Account<'info, CouncilBallot>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeCouncilElection<'info> {
+    #[account(
+        mut,
+        seeds = [COUNCIL_SEED],
+        bump
+    )]
+    pub council: Account<'info, Council>,
+
+    #[account(
+        mut,
+        seeds = [COUNCIL_ELECTION_SEED, election.number.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub election: Account<'info, CouncilElection>,
+}
+
+/// A sitting council member backing an action on a proposal. The action
+/// takes effect once a majority of the seated members back it.
+#[derive(Accounts)]
+pub struct CouncilProposalAction<'info> {
+    #[account(
+        seeds = [COUNCIL_SEED],
+        bump
+    )]
+    pub council: Account<'info, Council>,
+
+    #[account(mut)]
+    pub proposal_account: Account<'info, ProposalAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = member,
+        space = COUNCIL_MOTION_SPACE,
+        seeds = [COUNCIL_MOTION_SEED, proposal_account.key().as_ref()],
+        bump
+    )]
+    pub motion: Account<'info, CouncilMotion>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+////////////////////////////////////////////////////////////////
+//                     COUNCIL HANDLERS
+////////////////////////////////////////////////////////////////
+
+pub fn initialize_council(ctx: Context<InitializeCouncil>, seat_count: u8, term_days: i64) -> Result<()> {
+    require!(
+        seat_count > 0 && seat_count as usize <= MAX_COUNCIL_SEATS,
+        ErrorCode::InvalidCouncilConfig
+    );
+    require!((1..=MAX_COUNCIL_TERM_DAYS).contains(&term_days), ErrorCode::InvalidCouncilConfig);
+
+    let council = &mut ctx.accounts.council;
+    council.seats = vec![
+        CouncilSeat {
+            member: Pubkey::default(),
+            term_end: 0,
+        };
+        seat_count as usize
+    ];
+    council.term_days = term_days;
+    council.election_count = 0;
+    council.election_open = false;
+
+    Ok(())
+}
+
+pub fn open_council_election(ctx: Context<OpenCouncilElection>, deadline: i64) -> Result<()> {
+    require!(ctx.accounts.global_account.system_enabled, ErrorCode::CircuitBreakerTripped);
+    require_valid_deadline(deadline)?;
+
+    let council = &mut ctx.accounts.council;
+    require!(!council.election_open, ErrorCode::ElectionOpen);
+
+    // Seats whose term runs out before voting closes are contested too
+    let seats_up = council.seats.iter().filter(|seat| seat_vacant(seat, deadline)).count();
+    require!(seats_up > 0, ErrorCode::NoCouncilVacancy);
+
+    council.election_count += 1;
+    council.election_open = true;
+
+    let election = &mut ctx.accounts.election;
+    election.number = council.election_count;
+    election.seats_up = seats_up as u8;
+    election.deadline = deadline;
+    election.candidates = Vec::new();
+    election.finalized = false;
+
+    Ok(())
+}
+
+pub fn stand_for_council(ctx: Context<StandForCouncil>) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let candidate = ctx.accounts.candidate.key();
+
+    require!(Clock::get()?.unix_timestamp <= election.deadline, ErrorCode::ElectionClosed);
+    require!(
+        election.candidates.len() < MAX_ELECTION_CANDIDATES
+            && !election.candidates.iter().any(|entry| entry.candidate == candidate),
+        ErrorCode::InvalidCandidate
+    );
+
+    election.candidates.push(ElectionCandidate { candidate, votes: 0 });
+
+    Ok(())
+}
+
+/// Back one candidate with the voting power of the voter's stake locked
+/// past the election deadline. Liquid tokens do not count, and the positions
+/// counted are reserved to this ballot until the deadline, so neither can
+/// back a second ballot from another wallet. Each wallet casts a single
+/// ballot per election.
+pub fn vote_council_election(ctx: Context<VoteCouncilElection>, candidate: Pubkey) -> Result<()> {
+    require!(ctx.accounts.global_account.system_enabled, ErrorCode::CircuitBreakerTripped);
+
+    let election = &mut ctx.accounts.election;
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= election.deadline, ErrorCode::ElectionClosed);
+
+    let voter = ctx.accounts.user.key();
+    load_stake_positions(ctx.remaining_accounts, &voter, ctx.program_id)?;
+    let mut locked_positions: Vec<VoterStakeRecord> = Vec::new();
+    for info in ctx.remaining_accounts.iter().filter(|info| info.owner == ctx.program_id) {
+        let mut position = VoterStakeRecord::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        if position.lock_end_time <= election.deadline {
+            continue;
+        }
+        require!(info.is_writable, ErrorCode::InvalidStakePosition);
+        reserve_stake_position(&mut position, &voter, election.deadline, now)?;
+        position.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        locked_positions.push(position);
+    }
+    let power = calculate_voting_power(0, &locked_positions, now);
+    require!(power.total > 0, ErrorCode::NoVotingPower);
+
+    let entry = election
+        .candidates
+        .iter_mut()
+        .find(|entry| entry.candidate == candidate)
+        .ok_or(ErrorCode::InvalidCandidate)?;
+    entry.votes = entry.votes.checked_add(power.total).unwrap();
+
+    let ballot = &mut ctx.accounts.ballot;
+    ballot.election = election.key();
+    ballot.voter = ctx.accounts.user.key();
+    ballot.candidate = candidate;
+    ballot.voting_power = power.total;
+
+    Ok(())
+}
+
+/// Seat the leading candidates in the seats that are open once voting has
+/// closed. Anyone can call it after the deadline.
+pub fn finalize_council_election(ctx: Context<FinalizeCouncilElection>) -> Result<()> {
+    let election = &mut ctx.accounts.election;
+    let council = &mut ctx.accounts.council;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!election.finalized, ErrorCode::ElectionFinalized);
+    require!(now > election.deadline, ErrorCode::ProposalNotEnded);
+
+    // Most votes first; ties go to the earlier candidacy
+    let mut ranked = election.candidates.clone();
+    ranked.sort_by_key(|entry| std::cmp::Reverse(entry.votes));
+
+    let term_end = now.checked_add(days_to_seconds(council.term_days)).unwrap();
+    let mut seated: u8 = 0;
+    for entry in ranked.iter().filter(|entry| entry.votes > 0) {
+        if seated == election.seats_up {
+            break;
+        }
+        // A member mid-term keeps their current seat
+        if council.seats.iter().any(|seat| seat.member == entry.candidate && !seat_vacant(seat, now)) {
+            continue;
+        }
+        let Some(index) = council.seats.iter().position(|seat| seat_vacant(seat, now)) else {
+            break;
+        };
+
+        council.seats[index] = CouncilSeat {
+            member: entry.candidate,
+            term_end,
+        };
+        seated += 1;
+
+        emit!(CouncilMemberElected {
+            member: entry.candidate,
+            seat: index as u8,
+            term_end,
+        });
+    }
+
+    election.finalized = true;
+    council.election_open = false;
+
+    Ok(())
+}
+
+/// Guardian veto: stops voting on a proposal and blocks its execution once
+/// a majority of the council backs it. Escrowed treasury funds can then be
+/// reclaimed by the author.
+pub fn veto_proposal(ctx: Context<CouncilProposalAction>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let member = ctx.accounts.member.key();
+    require_council_member(&ctx.accounts.council, &member, now)?;
+
+    let proposal_account = &mut ctx.accounts.proposal_account;
+    require!(!proposal_account.executed, ErrorCode::AlreadyExecuted);
+    require!(!proposal_account.vetoed, ErrorCode::ProposalVetoed);
+
+    let motion = &mut ctx.accounts.motion;
+    motion.proposal = proposal_account.key();
+    if !approve_motion(&mut motion.veto_approvals, &ctx.accounts.council, member, now)? {
+        return Ok(());
+    }
+
+    proposal_account.vetoed = true;
+    proposal_account.is_active = false;
+
+    emit!(ProposalVetoed {
+        proposal: proposal_account.key(),
+        member: ctx.accounts.member.key(),
+    });
+
+    Ok(())
+}
+
+/// Shorten voting on an emergency proposal to `FAST_TRACK_VOTING_DAYS`
+/// from now and drop its timelock, once a majority of the council backs it.
+pub fn fast_track_proposal(ctx: Context<CouncilProposalAction>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let member = ctx.accounts.member.key();
+    require_council_member(&ctx.accounts.council, &member, now)?;
+
+    let proposal_account = &mut ctx.accounts.proposal_account;
+    require!(proposal_account.emergency, ErrorCode::NotEmergencyProposal);
+    require!(proposal_account.is_active, ErrorCode::ProposalNotActive);
+    require!(now <= proposal_account.deadline, ErrorCode::ProposalExpired);
+
+    let motion = &mut ctx.accounts.motion;
+    motion.proposal = proposal_account.key();
+    if !approve_motion(&mut motion.fast_track_approvals, &ctx.accounts.council, member, now)? {
+        return Ok(());
+    }
+
+    let fast_deadline = now.checked_add(days_to_seconds(FAST_TRACK_VOTING_DAYS)).unwrap();
+    proposal_account.deadline = proposal_account.deadline.min(fast_deadline);
+    proposal_account.timelock_seconds = 0;
+
+    emit!(ProposalFastTracked {
+        proposal: proposal_account.key(),
+        member: ctx.accounts.member.key(),
+        deadline: proposal_account.deadline,
+    });
+
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////
+//                     COUNCIL HELPERS
+////////////////////////////////////////////////////////////////
+
+/// A seat is vacant if it was never filled or its term has ended by `at`.
+fn seat_vacant(seat: &CouncilSeat, at: i64) -> bool {
+    seat.member == Pubkey::default() || seat.term_end <= at
+}
+
+/// Record `member`'s approval and report whether a majority of the seated
+/// members now back the motion. Approvals from members no longer seated are
+/// dropped.
+fn approve_motion(approvals: &mut Vec<Pubkey>, council: &Council, member: Pubkey, now: i64) -> Result<bool> {
+    approvals.retain(|approver| require_council_member(council, approver, now).is_ok());
+    require!(!approvals.contains(&member), ErrorCode::AlreadyVoted);
+    approvals.push(member);
+
+    let seated = council.seats.iter().filter(|seat| !seat_vacant(seat, now)).count();
+    Ok(approvals.len() * 2 > seated)
+}
+
+fn require_council_member(council: &Council, member: &Pubkey, now: i64) -> Result<()> {
+    require!(
+        council.seats.iter().any(|seat| seat.member == *member && !seat_vacant(seat, now)),
+        ErrorCode::NotCouncilMember
+    );
+    Ok(())
+}
+
+/// Whether `member` holds a seat on the council at `info`. An uninitialized
+/// council has no members.
+pub fn is_council_member(info: &AccountInfo, member: &Pubkey, program_id: &Pubkey) -> Result<bool> {
+    if info.data_is_empty() || info.owner != program_id {
+        return Ok(false);
+    }
+    let council = Council::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    Ok(require_council_member(&council, member, Clock::get()?.unix_timestamp).is_ok())
+}
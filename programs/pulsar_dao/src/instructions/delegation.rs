@@ -1,10 +1,52 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::error::ErrorCode;
-use crate::instructions::admin::GLOBAL_ACCOUNT_SEED;
+use crate::constants::days_to_seconds;
+use crate::instructions::admin::{DAO_CONFIG_SEED, GLOBAL_ACCOUNT_SEED, MAX_BPS, load_dao_config};
+use crate::instructions::council::{COUNCIL_SEED, is_council_member};
+use crate::instructions::rewards::{
+    REWARD_POOL_SEED, REWARD_VAULT_SEED, RewardVault, accrue_owner_positions, delegation_commission_bps,
+    pay_position_commission,
+};
+use crate::instructions::staking::{STAKER_PROFILE_SEED, load_all_stake_positions};
 
 pub const DELEGATE_PROFILE_SEED: &[u8] = b"delegate_profile";
 pub const DELEGATION_RECORD_SEED: &[u8] = b"delegation_record";
+pub const DELEGATE_PARTICIPATION_SEED: &[u8] = b"delegate_participation";
+
+pub const MAX_DELEGATE_SHARES: usize = 5;
+
+// Delegation scopes, one bit per proposal type
+pub const SCOPE_STANDARD: u32 = 1 << 0;
+pub const SCOPE_TREASURY: u32 = 1 << 1;
+pub const SCOPE_ALL: u32 = u32::MAX;
+
+pub const MAX_DISPLAY_NAME_LEN: usize = 32;
+pub const MAX_PLATFORM_URI_LEN: usize = 200;
+pub const MAX_FOCUS_TAGS: usize = 5;
+pub const MAX_FOCUS_TAG_LEN: usize = 16;
+pub const MAX_STATUS_REASON_LEN: usize = 64;
+
+// Space: 8 (discriminator) + 32 (authority) + 1 (is_active) + 4 + MAX_DISPLAY_NAME_LEN (display_name)
+//        + 4 + MAX_PLATFORM_URI_LEN (platform_uri) + 32 (platform_hash) + 4 + MAX_FOCUS_TAGS * (4 + MAX_FOCUS_TAG_LEN) (focus_tags)
+//        + 8 (delegator_count) + 8 (delegated_stake)
+//        + 8 (registered_proposal_count) + 8 (proposals_voted) + 8 (proposals_missed) + 8 (last_active_time)
+//        + 2 (commission_bps) + 2 (pending_commission_bps) + 8 (commission_effective_at)
+//        + 4 + MAX_STATUS_REASON_LEN (status_reason) + 8 (status_updated_at)
+pub const DELEGATE_PROFILE_SPACE: usize = 8 + 32 + 1 + 4 + MAX_DISPLAY_NAME_LEN
+    + 4 + MAX_PLATFORM_URI_LEN + 32 + 4 + MAX_FOCUS_TAGS * (4 + MAX_FOCUS_TAG_LEN)
+    + 8 + 8
+    + 8 + 8 + 8 + 8
+    + 2 + 2 + 8
+    + 4 + MAX_STATUS_REASON_LEN + 8;
+
+// Space: 8 (discriminator) + 32 (delegate) + 32 (proposal) + 1 (voted)
+pub const DELEGATE_PARTICIPATION_SPACE: usize = 8 + 32 + 32 + 1;
+
+// Space: 8 (discriminator) + 32 (delegator) + 4 (shares len) + MAX_DELEGATE_SHARES * (32 (delegate) + 2 (bps)) + 8 (delegated_stake)
+//        + 8 (expires_at) + 4 (scope) + 2 (commission_bps)
+pub const DELEGATION_RECORD_SPACE: usize = 8 + 32 + 4 + MAX_DELEGATE_SHARES * (32 + 2) + 8 + 8 + 4 + 2;
 
 ////////////////////////////////////////////////////////////////
 //                    DELEGATION CONTEXTS
@@ -21,8 +63,7 @@ pub struct RegisterDelegate<'info> {
     #[account(
         init,
         payer = admin,
-        // Space: 8 (discriminator) + 32 (authority) + 1 (is_active)
-        space = 8 + 32 + 1,
+        space = DELEGATE_PROFILE_SPACE,
         seeds = [DELEGATE_PROFILE_SEED, target_user.key().as_ref()],
         bump
     )]
@@ -41,16 +82,103 @@ pub struct RegisterDelegate<'info> {
     )]
     pub user_delegation_record: UncheckedAccount<'info>,
 
+    /// CHECK: Sitting council members may register delegates; may be uninitialized
+    #[account(
+        seeds = [COUNCIL_SEED],
+        bump
+    )]
+    pub council: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
+/// A candidate nominates themselves. The profile stays inactive until the
+/// admin approves it through `reinstate_delegate`.
+#[derive(Accounts)]
+pub struct NominateDelegate<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        space = DELEGATE_PROFILE_SPACE,
+        seeds = [DELEGATE_PROFILE_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub delegate_profile: Account<'info, DelegateProfile>,
+
+    /// CHECK: Validation check - Ensure candidate is not already a Delegator
+    #[account(
+        seeds = [DELEGATION_RECORD_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_delegation_record: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDelegateMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [DELEGATE_PROFILE_SEED, user.key().as_ref()],
+        bump,
+        constraint = delegate_profile.authority == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub delegate_profile: Account<'info, DelegateProfile>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetDelegateCommission<'info> {
+    /// CHECK: The DAO config, if initialized. Defaults apply until then (see `load_dao_config`).
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [DELEGATE_PROFILE_SEED, user.key().as_ref()],
+        bump,
+        constraint = delegate_profile.authority == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub delegate_profile: Account<'info, DelegateProfile>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DelegationTerms {
+    pub expires_at: i64,
+    pub scope: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DelegateMetadata {
+    pub display_name: String,
+    pub platform_uri: String,
+    pub platform_hash: [u8; 32],
+    pub focus_tags: Vec<String>,
+}
+
+/// The user's stake positions are passed through `remaining_accounts`,
+/// writable, starting with every position they opened in position id order.
+/// Rewards those positions earned so far are settled commission-free.
 #[derive(Accounts)]
 pub struct DelegateVote<'info> {
     #[account(
         init_if_needed,
         payer = user,
-        // Space: 8 (discriminator) + 32 (delegator) + 32 (delegate_target)
-        space = 8 + 32 + 32,
+        space = DELEGATION_RECORD_SPACE,
         seeds = [DELEGATION_RECORD_SEED, user.key().as_ref()],
         bump
     )]
@@ -59,18 +187,102 @@ pub struct DelegateVote<'info> {
     /// CHECK: The target delegate
     pub target_delegate: UncheckedAccount<'info>,
 
+    #[account(
+        mut,
+        seeds = [DELEGATE_PROFILE_SEED, target_delegate.key().as_ref()],
+        bump,
+        constraint = target_delegate_profile.is_active @ ErrorCode::InvalidDelegate
+    )]
+    pub target_delegate_profile: Account<'info, DelegateProfile>,
+
     /// CHECK: Validation check - Ensure User is not a Delegate (No chaining)
     #[account(
         seeds = [DELEGATE_PROFILE_SEED, user.key().as_ref()],
         bump
     )]
     pub user_delegate_profile: UncheckedAccount<'info>,
-    
+
+    /// CHECK: The DAO config, if initialized. Defaults apply until then (see `load_dao_config`).
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: UncheckedAccount<'info>,
+
+    /// CHECK: The user's staker profile, if any. Counts the positions to settle.
+    #[account(
+        seeds = [STAKER_PROFILE_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub staker_profile: UncheckedAccount<'info>,
+
+    /// CHECK: The reward pool, if created. The user's positions are settled against it.
+    #[account(
+        mut,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Splits the user's power across several delegates.
+/// Each delegate's `DelegateProfile` is passed through `remaining_accounts`,
+/// in the same order as the shares, followed by the user's stake positions
+/// as in `DelegateVote`.
+#[derive(Accounts)]
+pub struct DelegateSplit<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = DELEGATION_RECORD_SPACE,
+        seeds = [DELEGATION_RECORD_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub delegation_record: Account<'info, DelegationRecord>,
+
+    /// CHECK: Validation check - Ensure User is not a Delegate (No chaining)
+    #[account(
+        seeds = [DELEGATE_PROFILE_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_delegate_profile: UncheckedAccount<'info>,
+
+    /// CHECK: The DAO config, if initialized. Defaults apply until then (see `load_dao_config`).
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: UncheckedAccount<'info>,
+
+    /// CHECK: The user's staker profile, if any. Counts the positions to settle.
+    #[account(
+        seeds = [STAKER_PROFILE_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub staker_profile: UncheckedAccount<'info>,
+
+    /// CHECK: The reward pool, if created. The user's positions are settled against it.
+    #[account(
+        mut,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+/// Each delegate's `(delegate_profile, delegate_token_account)` pair is
+/// passed through `remaining_accounts`, in share order, followed by every
+/// stake position the user opened, writable and in position id order. The
+/// commission set aside on those positions is paid out before the record
+/// closes, so the reward vault accounts are needed once rewards have accrued.
 #[derive(Accounts)]
 pub struct RevokeDelegation<'info> {
     #[account(
@@ -80,22 +292,145 @@ pub struct RevokeDelegation<'info> {
         close = user
     )]
     pub delegation_record: Account<'info, DelegationRecord>,
+
+    /// CHECK: The DAO config, if initialized. Defaults apply until then (see `load_dao_config`).
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: UncheckedAccount<'info>,
+
+    /// CHECK: The user's staker profile, if any. Counts the positions to settle.
+    #[account(
+        seeds = [STAKER_PROFILE_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub staker_profile: UncheckedAccount<'info>,
+
+    /// CHECK: The reward pool, if created. The user's positions are settled against it.
+    #[account(
+        mut,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    /// Verified against the reward vault seeds in the handler
+    #[account(mut)]
+    pub reward_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 }
 
+/// Records a closed proposal the delegate never voted on. Anyone may call it.
 #[derive(Accounts)]
-pub struct RemoveDelegate<'info> {
+pub struct RecordMissedProposal<'info> {
+    pub proposal_account: Account<'info, ProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [DELEGATE_PROFILE_SEED, delegate.key().as_ref()],
+        bump
+    )]
+    pub delegate_profile: Account<'info, DelegateProfile>,
+
+    /// CHECK: Verified by the delegate profile seeds
+    pub delegate: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = DELEGATE_PARTICIPATION_SPACE,
+        seeds = [DELEGATE_PARTICIPATION_SEED, proposal_account.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub participation: Account<'info, DelegateParticipation>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Re-credits the delegates after the delegator's stake changed.
+/// Each delegate's `DelegateProfile` is passed through `remaining_accounts`,
+/// in the same order as the shares, followed by every stake position the
+/// delegator opened (see `load_all_stake_positions`).
+#[derive(Accounts)]
+pub struct ResyncDelegation<'info> {
+    #[account(
+        mut,
+        seeds = [DELEGATION_RECORD_SEED, delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation_record: Account<'info, DelegationRecord>,
+
+    #[account(
+        seeds = [STAKER_PROFILE_SEED, delegator.key().as_ref()],
+        bump
+    )]
+    pub staker_profile: Account<'info, StakerProfile>,
+
+    /// CHECK: Verified by the delegation record seeds
+    pub delegator: UncheckedAccount<'info>,
+
+    /// The delegator or one of their delegates
+    pub user: Signer<'info>,
+}
+
+/// Delegation records naming this delegate are passed through
+/// `remaining_accounts` and voided, each followed by the profiles of the
+/// record's other delegates.
+#[derive(Accounts)]
+pub struct SetDelegateStatus<'info> {
     #[account(
         seeds = [GLOBAL_ACCOUNT_SEED],
         bump
     )]
     pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        mut,
+        seeds = [DELEGATE_PROFILE_SEED, target_user.key().as_ref()],
+        bump
+    )]
+    pub delegate_profile: Account<'info, DelegateProfile>,
+
+    /// CHECK: The delegate being suspended or reinstated
+    pub target_user: UncheckedAccount<'info>,
+
+    /// CHECK: Sitting council members may suspend and reinstate; may be uninitialized
+    #[account(
+        seeds = [COUNCIL_SEED],
+        bump
+    )]
+    pub council: UncheckedAccount<'info>,
+
+    /// The admin or a sitting council member
+    pub admin: Signer<'info>,
+}
+
+/// Only a suspended delegate can be removed. Delegation records still
+/// naming them are passed through `remaining_accounts` and voided, each
+/// followed by the profiles of the record's other delegates. Every such
+/// record must be passed: the profile is only closed once no delegator
+/// remains.
+#[derive(Accounts)]
+pub struct RemoveDelegate<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump,
+        constraint = global_account.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
     
     #[account(
         mut,
         seeds = [DELEGATE_PROFILE_SEED, target_user.key().as_ref()],
         bump,
+        constraint = !delegate_profile.is_active @ ErrorCode::DelegateNotSuspended,
         close = admin
     )]
     pub delegate_profile: Account<'info, DelegateProfile>,
@@ -111,46 +446,569 @@ pub struct RemoveDelegate<'info> {
 //                    DELEGATION HANDLERS
 ////////////////////////////////////////////////////////////////
 
+/// Register a delegate. Callable by the admin or a sitting council member.
 pub fn register_delegate(ctx: Context<RegisterDelegate>) -> Result<()> {
     let global_account = &ctx.accounts.global_account;
-    require!(global_account.admin == ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+    let signer = ctx.accounts.admin.key();
+    require!(
+        global_account.admin == signer || is_council_member(&ctx.accounts.council, &signer, ctx.program_id)?,
+        ErrorCode::Unauthorized
+    );
 
     let delegate_profile = &mut ctx.accounts.delegate_profile;
     let delegation_record = &ctx.accounts.user_delegation_record;
     
     // Prevent Delegator -> Delegate (must not be a delegator)
-    let is_delegator = delegation_record.to_account_info().lamports() > 0 && delegation_record.owner == ctx.program_id;
-    require!(!is_delegator, ErrorCode::DelegatorCannotBeDelegate);
+    require!(!is_delegating(delegation_record, ctx.program_id)?, ErrorCode::DelegatorCannotBeDelegate);
 
     delegate_profile.authority = ctx.accounts.target_user.key();
     delegate_profile.is_active = true;
+    delegate_profile.registered_proposal_count = global_account.proposal_count;
     
     Ok(())
 }
 
-pub fn delegate_vote(ctx: Context<DelegateVote>) -> Result<()> {
+pub fn nominate_delegate(ctx: Context<NominateDelegate>, metadata: DelegateMetadata) -> Result<()> {
+    // Prevent Delegator -> Delegate (must not be a delegator)
+    require!(
+        !is_delegating(&ctx.accounts.user_delegation_record, ctx.program_id)?,
+        ErrorCode::DelegatorCannotBeDelegate
+    );
+
+    let delegate_profile = &mut ctx.accounts.delegate_profile;
+    delegate_profile.authority = ctx.accounts.user.key();
+    delegate_profile.is_active = false;
+    delegate_profile.registered_proposal_count = ctx.accounts.global_account.proposal_count;
+    apply_delegate_metadata(delegate_profile, metadata)
+}
+
+pub fn update_delegate_metadata(ctx: Context<UpdateDelegateMetadata>, metadata: DelegateMetadata) -> Result<()> {
+    apply_delegate_metadata(&mut ctx.accounts.delegate_profile, metadata)
+}
+
+/// Schedule a new commission. It takes effect after the configured notice
+/// period, so delegators can react before they are charged.
+pub fn set_delegate_commission(ctx: Context<SetDelegateCommission>, commission_bps: u16) -> Result<()> {
+    let dao_config = load_dao_config(&ctx.accounts.dao_config, ctx.program_id)?;
+    require!(commission_bps <= dao_config.max_delegate_commission_bps, ErrorCode::CommissionTooHigh);
+
+    let delegate_profile = &mut ctx.accounts.delegate_profile;
+    let now = Clock::get()?.unix_timestamp;
+
+    // A change whose notice has passed is settled before scheduling the next
+    delegate_profile.commission_bps = effective_commission_bps(delegate_profile, now);
+    delegate_profile.pending_commission_bps = commission_bps;
+    delegate_profile.commission_effective_at = now
+        .checked_add(days_to_seconds(dao_config.commission_notice_days))
+        .unwrap();
+
+    Ok(())
+}
+
+/// Delegate all of the user's power to one delegate.
+pub fn delegate_vote(ctx: Context<DelegateVote>, terms: DelegationTerms) -> Result<()> {
+    require_not_delegate(&ctx.accounts.user_delegate_profile, ctx.program_id)?;
+    require!(ctx.accounts.delegation_record.shares.is_empty(), ErrorCode::DelegationActive);
+    validate_delegation_terms(&terms)?;
+
+    let shares = vec![DelegateShare {
+        delegate: ctx.accounts.target_delegate.key(),
+        bps: MAX_BPS,
+    }];
+    validate_delegate_shares(&shares, &ctx.accounts.user.key())?;
+
+    let user = ctx.accounts.user.key();
+    let position_count = opened_position_count(&ctx.accounts.staker_profile, ctx.program_id)?;
+    let stake = delegator_stake(ctx.remaining_accounts, &user, position_count, ctx.program_id)?;
+    credit_delegate(&mut ctx.accounts.target_delegate_profile, share_stake(stake, MAX_BPS));
+
+    let now = Clock::get()?.unix_timestamp;
+    let max_commission_bps = load_dao_config(&ctx.accounts.dao_config, ctx.program_id)?.max_delegate_commission_bps;
+    let commission_bps = effective_commission_bps(&ctx.accounts.target_delegate_profile, now).min(max_commission_bps);
+
+    // Rewards earned before the delegation carry no commission
+    accrue_owner_positions(
+        ctx.remaining_accounts,
+        &user,
+        position_count,
+        &ctx.accounts.reward_pool,
+        0,
+        ctx.program_id,
+    )?;
+
     let delegation_record = &mut ctx.accounts.delegation_record;
-    let delegate_profile = &ctx.accounts.user_delegate_profile;
+    delegation_record.delegator = user;
+    delegation_record.shares = shares;
+    delegation_record.delegated_stake = stake;
+    delegation_record.expires_at = terms.expires_at;
+    delegation_record.scope = terms.scope;
+    delegation_record.commission_bps = commission_bps;
 
-    // Prevent Delegate -> Delegate (chain)
-    let is_delegate = delegate_profile.to_account_info().lamports() > 0 && delegate_profile.owner == ctx.program_id;
-    require!(!is_delegate, ErrorCode::DelegateCannotDelegate);
-    
-    // Prevent self-delegation
-    require!(ctx.accounts.user.key() != ctx.accounts.target_delegate.key(), ErrorCode::DelegationLoop);
+    Ok(())
+}
+
+/// Delegate the user's power in basis-point shares to several delegates.
+pub fn delegate_split(ctx: Context<DelegateSplit>, shares: Vec<DelegateShare>, terms: DelegationTerms) -> Result<()> {
+    require_not_delegate(&ctx.accounts.user_delegate_profile, ctx.program_id)?;
+    require!(ctx.accounts.delegation_record.shares.is_empty(), ErrorCode::DelegationActive);
+    validate_delegate_shares(&shares, &ctx.accounts.user.key())?;
+    validate_delegation_terms(&terms)?;
+
+    require!(ctx.remaining_accounts.len() >= shares.len(), ErrorCode::InvalidDelegate);
+    let (profile_accounts, position_accounts) = ctx.remaining_accounts.split_at(shares.len());
+
+    let user = ctx.accounts.user.key();
+    let position_count = opened_position_count(&ctx.accounts.staker_profile, ctx.program_id)?;
+    let stake = delegator_stake(position_accounts, &user, position_count, ctx.program_id)?;
 
-    delegation_record.delegator = ctx.accounts.user.key();
-    delegation_record.delegate_target = ctx.accounts.target_delegate.key();
+    let now = Clock::get()?.unix_timestamp;
+    let max_commission_bps = load_dao_config(&ctx.accounts.dao_config, ctx.program_id)?.max_delegate_commission_bps;
+    let mut weighted_commission: u32 = 0;
+    for (share, profile_info) in shares.iter().zip(profile_accounts.iter()) {
+        require_active_delegate(profile_info, &share.delegate, ctx.program_id)?;
+        update_delegate_profile(profile_info, &share.delegate, ctx.program_id, |profile| {
+            let commission_bps = effective_commission_bps(profile, now).min(max_commission_bps);
+            weighted_commission += share.bps as u32 * commission_bps as u32;
+            credit_delegate(profile, share_stake(stake, share.bps))
+        })?;
+    }
+
+    // Rewards earned before the delegation carry no commission
+    accrue_owner_positions(
+        position_accounts,
+        &user,
+        position_count,
+        &ctx.accounts.reward_pool,
+        0,
+        ctx.program_id,
+    )?;
+
+    let delegation_record = &mut ctx.accounts.delegation_record;
+    delegation_record.delegator = user;
+    delegation_record.shares = shares;
+    delegation_record.delegated_stake = stake;
+    delegation_record.expires_at = terms.expires_at;
+    delegation_record.scope = terms.scope;
+    delegation_record.commission_bps = (weighted_commission / MAX_BPS as u32) as u16;
 
     Ok(())
 }
 
-pub fn revoke_delegation(_ctx: Context<RevokeDelegation>) -> Result<()> {
+/// End the delegation, first paying the delegates the commission charged
+/// on rewards the user's positions earned under it.
+pub fn revoke_delegation<'info>(ctx: Context<'_, '_, 'info, 'info, RevokeDelegation<'info>>) -> Result<()> {
+    let share_count = ctx.accounts.delegation_record.shares.len();
+    require!(ctx.remaining_accounts.len() >= share_count * 2, ErrorCode::InvalidDelegate);
+    let (commission_accounts, position_accounts) = ctx.remaining_accounts.split_at(share_count * 2);
+
+    let user = ctx.accounts.user.key();
+    let record_info = ctx.accounts.delegation_record.to_account_info();
+    let position_count = opened_position_count(&ctx.accounts.staker_profile, ctx.program_id)?;
+    accrue_owner_positions(
+        position_accounts,
+        &user,
+        position_count,
+        &ctx.accounts.reward_pool,
+        delegation_commission_bps(&record_info, ctx.program_id)?,
+        ctx.program_id,
+    )?;
+
+    // Positions were verified by `accrue_owner_positions`
+    let max_commission_bps = load_dao_config(&ctx.accounts.dao_config, ctx.program_id)?.max_delegate_commission_bps;
+    let mut available = ctx.accounts.reward_vault.as_ref().map_or(0, |vault| vault.amount);
+    let owned_positions = position_accounts
+        .iter()
+        .filter(|info| info.owner == ctx.program_id)
+        .take(position_count as usize);
+    for info in owned_positions {
+        let mut stake_record = VoterStakeRecord::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        if stake_record.pending_commission == 0 {
+            continue;
+        }
+        let vault = reward_vault(ctx.accounts, ctx.program_id)?;
+        let paid = pay_position_commission(
+            &mut stake_record,
+            &record_info,
+            commission_accounts,
+            max_commission_bps,
+            available,
+            false,
+            &vault,
+            ctx.program_id,
+        )?;
+        available -= paid;
+        stake_record.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+
+    let delegation_record = &ctx.accounts.delegation_record;
+    for (share, pair) in delegation_record.shares.iter().zip(commission_accounts.chunks(2)) {
+        update_delegate_profile(&pair[0], &share.delegate, ctx.program_id, |profile| {
+            debit_delegate(profile, share_stake(delegation_record.delegated_stake, share.bps))
+        })?;
+    }
+
     // Account closure handled by Anchor's `close` constraint
     Ok(())
 }
 
-pub fn remove_delegate(_ctx: Context<RemoveDelegate>) -> Result<()> {
+/// Count a proposal the delegate let close without a proxy vote.
+pub fn record_missed_proposal(ctx: Context<RecordMissedProposal>) -> Result<()> {
+    let proposal_account = &ctx.accounts.proposal_account;
+    let delegate_profile = &mut ctx.accounts.delegate_profile;
+    let participation = &mut ctx.accounts.participation;
+    let clock = Clock::get()?;
+
+    require!(clock.unix_timestamp > proposal_account.deadline, ErrorCode::ProposalNotEnded);
+    require!(
+        proposal_account.number > delegate_profile.registered_proposal_count,
+        ErrorCode::ProposalPredatesDelegate
+    );
+    require!(participation.proposal == Pubkey::default(), ErrorCode::ParticipationRecorded);
+
+    participation.delegate = ctx.accounts.delegate.key();
+    participation.proposal = proposal_account.key();
+    participation.voted = false;
+    delegate_profile.proposals_missed = delegate_profile.proposals_missed.checked_add(1).unwrap();
+
+    Ok(())
+}
+
+/// Re-credit the delegates with the delegator's current stake.
+pub fn resync_delegation(ctx: Context<ResyncDelegation>) -> Result<()> {
+    let delegation_record = &mut ctx.accounts.delegation_record;
+    let user = ctx.accounts.user.key();
+    require!(
+        user == delegation_record.delegator || delegate_share_bps(delegation_record, &user).is_some(),
+        ErrorCode::Unauthorized
+    );
+
+    let share_count = delegation_record.shares.len();
+    require!(ctx.remaining_accounts.len() >= share_count, ErrorCode::InvalidDelegate);
+    let (profile_accounts, position_accounts) = ctx.remaining_accounts.split_at(share_count);
+
+    let old_stake = delegation_record.delegated_stake;
+    let staker_profile = &ctx.accounts.staker_profile;
+    let new_stake = load_all_stake_positions(position_accounts, &staker_profile.owner, staker_profile.position_count, ctx.program_id)?
+        .iter()
+        .fold(0u64, |total, position| total.checked_add(position.staked_amount).unwrap());
+    for (share, profile_info) in delegation_record.shares.iter().zip(profile_accounts.iter()) {
+        update_delegate_profile(profile_info, &share.delegate, ctx.program_id, |profile| {
+            profile.delegated_stake = profile
+                .delegated_stake
+                .checked_sub(share_stake(old_stake, share.bps))
+                .unwrap()
+                .checked_add(share_stake(new_stake, share.bps))
+                .unwrap();
+        })?;
+    }
+    delegation_record.delegated_stake = new_stake;
+
+    Ok(())
+}
+
+/// Suspend an active delegate, voiding the delegations passed in
+/// `remaining_accounts`. Proxy votes already cast stay until cleared with
+/// `clear_proxy_votes`.
+pub fn suspend_delegate(ctx: Context<SetDelegateStatus>, reason: String) -> Result<()> {
+    require!(ctx.accounts.delegate_profile.is_active, ErrorCode::InvalidDelegate);
+    set_delegate_status(ctx.accounts, false, reason, ctx.program_id)?;
+    void_delegations(ctx.remaining_accounts, &mut ctx.accounts.delegate_profile, ctx.program_id)
+}
+
+/// Lift a suspension, or approve a self-nominated delegate.
+pub fn reinstate_delegate(ctx: Context<SetDelegateStatus>, reason: String) -> Result<()> {
+    require!(!ctx.accounts.delegate_profile.is_active, ErrorCode::DelegateNotSuspended);
+    set_delegate_status(ctx.accounts, true, reason, ctx.program_id)
+}
+
+pub fn remove_delegate(ctx: Context<RemoveDelegate>) -> Result<()> {
+    void_delegations(ctx.remaining_accounts, &mut ctx.accounts.delegate_profile, ctx.program_id)?;
+    require!(ctx.accounts.delegate_profile.delegator_count == 0, ErrorCode::DelegatorsRemain);
     // Account closure handled by Anchor's `close` constraint
     Ok(())
 }
+
+////////////////////////////////////////////////////////////////
+//                    DELEGATION HELPERS
+////////////////////////////////////////////////////////////////
+
+/// Prevent Delegate -> Delegate (chain)
+fn require_not_delegate(user_delegate_profile: &UncheckedAccount, program_id: &Pubkey) -> Result<()> {
+    let is_delegate = user_delegate_profile.lamports() > 0 && user_delegate_profile.owner == program_id;
+    require!(!is_delegate, ErrorCode::DelegateCannotDelegate);
+    Ok(())
+}
+
+/// Shares must name distinct delegates other than the delegator, and sum
+/// to exactly 100%.
+fn validate_delegate_shares(shares: &[DelegateShare], delegator: &Pubkey) -> Result<()> {
+    require!(
+        !shares.is_empty() && shares.len() <= MAX_DELEGATE_SHARES,
+        ErrorCode::InvalidDelegationShares
+    );
+
+    let mut total_bps: u32 = 0;
+    for (index, share) in shares.iter().enumerate() {
+        // Prevent self-delegation
+        require!(share.delegate != *delegator, ErrorCode::DelegationLoop);
+        require!(share.bps > 0, ErrorCode::InvalidDelegationShares);
+        require!(
+            !shares[..index].iter().any(|other| other.delegate == share.delegate),
+            ErrorCode::InvalidDelegationShares
+        );
+        total_bps += share.bps as u32;
+    }
+    require!(total_bps == MAX_BPS as u32, ErrorCode::InvalidDelegationShares);
+
+    Ok(())
+}
+
+fn validate_delegation_terms(terms: &DelegationTerms) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        (terms.expires_at == 0 || terms.expires_at > now) && terms.scope != 0,
+        ErrorCode::InvalidDelegationTerms
+    );
+    Ok(())
+}
+
+/// The delegation must be unexpired and cover the proposal's type.
+pub fn require_delegation_covers(delegation_record: &DelegationRecord, proposal_account: &ProposalAccount, now: i64) -> Result<()> {
+    require!(!delegation_expired(delegation_record, now), ErrorCode::DelegationExpired);
+
+    let type_bit = 1u32.checked_shl(proposal_account.proposal_type as u32).unwrap_or(0);
+    require!(delegation_record.scope & type_bit != 0, ErrorCode::ProposalOutOfScope);
+    Ok(())
+}
+
+fn delegation_expired(delegation_record: &DelegationRecord, now: i64) -> bool {
+    delegation_record.expires_at != 0 && now >= delegation_record.expires_at
+}
+
+fn apply_delegate_metadata(delegate_profile: &mut DelegateProfile, metadata: DelegateMetadata) -> Result<()> {
+    require!(
+        !metadata.display_name.is_empty() && metadata.display_name.len() <= MAX_DISPLAY_NAME_LEN,
+        ErrorCode::InvalidDelegateMetadata
+    );
+    require!(metadata.platform_uri.len() <= MAX_PLATFORM_URI_LEN, ErrorCode::InvalidDelegateMetadata);
+    require!(
+        metadata.focus_tags.len() <= MAX_FOCUS_TAGS
+            && metadata.focus_tags.iter().all(|tag| !tag.is_empty() && tag.len() <= MAX_FOCUS_TAG_LEN),
+        ErrorCode::InvalidDelegateMetadata
+    );
+
+    delegate_profile.display_name = metadata.display_name;
+    delegate_profile.platform_uri = metadata.platform_uri;
+    delegate_profile.platform_hash = metadata.platform_hash;
+    delegate_profile.focus_tags = metadata.focus_tags;
+    Ok(())
+}
+
+/// Whether `delegation_record` holds a live delegation. A closed or
+/// expired record, or one voided by a delegate's removal, does not.
+pub fn is_delegating(delegation_record: &AccountInfo, program_id: &Pubkey) -> Result<bool> {
+    if delegation_record.data_is_empty() || delegation_record.owner != program_id {
+        return Ok(false);
+    }
+    let record = DelegationRecord::try_deserialize(&mut &delegation_record.try_borrow_data()?[..])?;
+    let now = Clock::get()?.unix_timestamp;
+    Ok(!record.shares.is_empty() && !delegation_expired(&record, now))
+}
+
+/// The delegate must have an active `DelegateProfile`.
+fn require_active_delegate(profile_info: &AccountInfo, delegate: &Pubkey, program_id: &Pubkey) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(&[DELEGATE_PROFILE_SEED, delegate.as_ref()], program_id);
+    require_keys_eq!(*profile_info.key, expected, ErrorCode::InvalidDelegate);
+    require_keys_eq!(*profile_info.owner, *program_id, ErrorCode::InvalidDelegate);
+
+    let profile = DelegateProfile::try_deserialize(&mut &profile_info.try_borrow_data()?[..])?;
+    require!(profile.is_active, ErrorCode::InvalidDelegate);
+    Ok(())
+}
+
+/// Clear every share of the given delegation records and debit each
+/// delegate they named. Each record must name `delegate_profile` and is
+/// followed by the profiles of its other delegates, in share order.
+fn void_delegations(accounts: &[AccountInfo], delegate_profile: &mut DelegateProfile, program_id: &Pubkey) -> Result<()> {
+    let delegate = delegate_profile.authority;
+    let mut iter = accounts.iter();
+    while let Some(record_info) = iter.next() {
+        require!(record_info.is_writable, ErrorCode::InvalidDelegationRecord);
+        require_keys_eq!(*record_info.owner, *program_id, ErrorCode::InvalidDelegationRecord);
+
+        let mut record = DelegationRecord::try_deserialize(&mut &record_info.try_borrow_data()?[..])?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[DELEGATION_RECORD_SEED, record.delegator.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(*record_info.key, expected, ErrorCode::InvalidDelegationRecord);
+        require!(delegate_share_bps(&record, &delegate).is_some(), ErrorCode::InvalidDelegationRecord);
+
+        for share in record.shares.iter() {
+            let stake = share_stake(record.delegated_stake, share.bps);
+            if share.delegate == delegate {
+                debit_delegate(delegate_profile, stake);
+            } else {
+                let profile_info = iter.next().ok_or(ErrorCode::InvalidDelegate)?;
+                update_delegate_profile(profile_info, &share.delegate, program_id, |profile| {
+                    debit_delegate(profile, stake)
+                })?;
+            }
+        }
+
+        record.shares.clear();
+        record.delegated_stake = 0;
+        record.commission_bps = 0;
+        record.try_serialize(&mut &mut record_info.try_borrow_mut_data()?[..])?;
+
+        emit!(DelegationVoided {
+            delegator: record.delegator,
+            delegate,
+        });
+    }
+    Ok(())
+}
+
+/// Callable by the admin or a sitting council member.
+fn set_delegate_status(
+    accounts: &mut SetDelegateStatus,
+    is_active: bool,
+    reason: String,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let signer = accounts.admin.key();
+    require!(
+        accounts.global_account.admin == signer || is_council_member(&accounts.council, &signer, program_id)?,
+        ErrorCode::Unauthorized
+    );
+    require!(reason.len() <= MAX_STATUS_REASON_LEN, ErrorCode::InvalidDelegateMetadata);
+
+    let delegate_profile = &mut accounts.delegate_profile;
+    delegate_profile.is_active = is_active;
+    delegate_profile.status_reason = reason.clone();
+    delegate_profile.status_updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(DelegateStatusChanged {
+        delegate: delegate_profile.authority,
+        is_active,
+        reason,
+    });
+
+    Ok(())
+}
+
+/// Mark the delegate active, counting the proposal the first time they
+/// vote on it as proxy.
+pub fn record_proxy_participation(
+    delegate_profile: &mut DelegateProfile,
+    participation: &mut DelegateParticipation,
+    proposal: Pubkey,
+    now: i64,
+) {
+    if participation.proposal == Pubkey::default() {
+        participation.delegate = delegate_profile.authority;
+        participation.proposal = proposal;
+        participation.voted = true;
+        delegate_profile.proposals_voted = delegate_profile.proposals_voted.checked_add(1).unwrap();
+    }
+    delegate_profile.last_active_time = now;
+}
+
+/// The commission charged at `now`, honouring any scheduled change.
+pub fn effective_commission_bps(delegate_profile: &DelegateProfile, now: i64) -> u16 {
+    if delegate_profile.commission_effective_at != 0 && now >= delegate_profile.commission_effective_at {
+        delegate_profile.pending_commission_bps
+    } else {
+        delegate_profile.commission_bps
+    }
+}
+
+/// The share (basis points) of the delegator's power held by `delegate`.
+pub fn delegate_share_bps(delegation_record: &DelegationRecord, delegate: &Pubkey) -> Option<u16> {
+    delegation_record
+        .shares
+        .iter()
+        .find(|share| share.delegate == *delegate)
+        .map(|share| share.bps)
+}
+
+/// The number of positions the owner of `staker_profile_info` opened; none
+/// before their first deposit.
+fn opened_position_count(staker_profile_info: &AccountInfo, program_id: &Pubkey) -> Result<u64> {
+    if staker_profile_info.data_is_empty() || staker_profile_info.owner != program_id {
+        return Ok(0);
+    }
+    let staker_profile = StakerProfile::try_deserialize(&mut &staker_profile_info.try_borrow_data()?[..])?;
+    Ok(staker_profile.position_count)
+}
+
+/// The reward vault passed to `revoke_delegation`, checked against its seeds.
+fn reward_vault<'a, 'info>(
+    accounts: &'a RevokeDelegation<'info>,
+    program_id: &Pubkey,
+) -> Result<RewardVault<'a, 'info>> {
+    let (Some(reward_vault), Some(token_mint), Some(token_program)) =
+        (&accounts.reward_vault, &accounts.token_mint, &accounts.token_program)
+    else {
+        return err!(ErrorCode::InvalidTokenAccount);
+    };
+    let (expected, bump) = Pubkey::find_program_address(&[REWARD_VAULT_SEED, token_mint.key().as_ref()], program_id);
+    require_keys_eq!(reward_vault.key(), expected, ErrorCode::InvalidTokenAccount);
+    Ok(RewardVault {
+        reward_vault,
+        bump,
+        token_mint,
+        token_program,
+    })
+}
+
+/// Staked tokens across the positions the delegator holds: those they
+/// opened and kept, and any they bought. Unbonding tokens are not counted.
+fn delegator_stake(
+    position_accounts: &[AccountInfo],
+    delegator: &Pubkey,
+    position_count: u64,
+    program_id: &Pubkey,
+) -> Result<u64> {
+    let positions = load_all_stake_positions(position_accounts, delegator, position_count, program_id)?;
+    Ok(positions
+        .iter()
+        .fold(0u64, |total, position| total.checked_add(position.staked_amount).unwrap()))
+}
+
+/// The part of `stake` carried by a share of `bps`.
+fn share_stake(stake: u64, bps: u16) -> u64 {
+    (stake as u128 * bps as u128 / MAX_BPS as u128) as u64
+}
+
+fn credit_delegate(delegate_profile: &mut DelegateProfile, stake: u64) {
+    delegate_profile.delegator_count = delegate_profile.delegator_count.checked_add(1).unwrap();
+    delegate_profile.delegated_stake = delegate_profile.delegated_stake.checked_add(stake).unwrap();
+}
+
+fn debit_delegate(delegate_profile: &mut DelegateProfile, stake: u64) {
+    delegate_profile.delegator_count = delegate_profile.delegator_count.checked_sub(1).unwrap();
+    delegate_profile.delegated_stake = delegate_profile.delegated_stake.checked_sub(stake).unwrap();
+}
+
+/// Apply `update` to the `DelegateProfile` of `delegate` passed as a raw
+/// account. A profile closed by `remove_delegate` is skipped.
+fn update_delegate_profile(
+    profile_info: &AccountInfo,
+    delegate: &Pubkey,
+    program_id: &Pubkey,
+    update: impl FnOnce(&mut DelegateProfile),
+) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(&[DELEGATE_PROFILE_SEED, delegate.as_ref()], program_id);
+    require_keys_eq!(*profile_info.key, expected, ErrorCode::InvalidDelegate);
+    if profile_info.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*profile_info.owner, *program_id, ErrorCode::InvalidDelegate);
+    require!(profile_info.is_writable, ErrorCode::InvalidDelegate);
+
+    let mut profile = DelegateProfile::try_deserialize(&mut &profile_info.try_borrow_data()?[..])?;
+    update(&mut profile);
+    profile.try_serialize(&mut &mut profile_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, Mint, TokenAccount, self};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::metadata::Metadata;
 use anchor_spl::metadata::{create_metadata_accounts_v3, CreateMetadataAccountsV3};
@@ -8,11 +8,13 @@ use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
 
 use crate::state::*;
 use crate::error::ErrorCode;
+use crate::constants::days_to_seconds;
 use crate::instructions::admin::GLOBAL_ACCOUNT_SEED;
 use crate::instructions::voting::USER_STATS_SEED;
 
 pub const FAUCET_SEED: &[u8] = b"faucet";
 pub const BADGE_MINT_SEED: &[u8] = b"badge";
+pub const FAUCET_COOLDOWN_DAYS: i64 = 1;
 
 ////////////////////////////////////////////////////////////////
 //                   GAMIFICATION CONTEXTS
@@ -40,7 +42,7 @@ pub struct RequestTokens<'info> {
         mut,
         constraint = token_mint.mint_authority.unwrap() == global_account.key()
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         init_if_needed,
@@ -48,11 +50,11 @@ pub struct RequestTokens<'info> {
         associated_token::mint = token_mint,
         associated_token::authority = user
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(mut)]
     pub user: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -76,7 +78,7 @@ pub struct ClaimBadge<'info> {
         mint::authority = user_stats,
         mint::freeze_authority = user_stats,
     )]
-    pub badge_mint: Account<'info, Mint>,
+    pub badge_mint: InterfaceAccount<'info, Mint>,
 
     /// CHECK: Metaplex Metadata Account (Derived from badge_mint)
     #[account(mut)]
@@ -92,12 +94,12 @@ pub struct ClaimBadge<'info> {
         associated_token::mint = badge_mint,
         associated_token::authority = user
     )]
-    pub user_badge_token_account: Account<'info, TokenAccount>,
+    pub user_badge_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
@@ -115,17 +117,17 @@ pub fn request_tokens(ctx: Context<RequestTokens>) -> Result<()> {
     
     if faucet_record.last_request_time > 0 {
         require!(
-            current_time >= faucet_record.last_request_time + 86400, 
+            current_time >= faucet_record.last_request_time + days_to_seconds(FAUCET_COOLDOWN_DAYS),
             ErrorCode::FaucetCooldown
         );
     }
 
     let amount = 3000 * 10u64.pow(ctx.accounts.token_mint.decimals as u32);
     
-    token::mint_to(
+    token_interface::mint_to(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            token::MintTo {
+            token_interface::MintTo {
                 mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.user_token_account.to_account_info(),
                 authority: ctx.accounts.global_account.to_account_info(),
@@ -170,10 +172,10 @@ pub fn claim_badge(ctx: Context<ClaimBadge>) -> Result<()> {
     }
 
     // 2. Mint the NFT (Token)
-    token::mint_to(
+    token_interface::mint_to(
         CpiContext::new_with_signer(
             token_program_info.clone(),
-            token::MintTo {
+            token_interface::MintTo {
                 mint: badge_mint_info.clone(),
                 to: ctx.accounts.user_badge_token_account.to_account_info(),
                 authority: user_stats_info.clone(),
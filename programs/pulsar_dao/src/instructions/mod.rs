@@ -5,6 +5,8 @@ pub mod voting;
 pub mod treasury;
 pub mod delegation;
 pub mod gamification;
+pub mod rewards;
+pub mod council;
 
 pub use admin::*;
 pub use staking::*;
@@ -13,3 +15,5 @@ pub use voting::*;
 pub use treasury::*;
 pub use delegation::*;
 pub use gamification::*;
+pub use rewards::*;
+pub use council::*;
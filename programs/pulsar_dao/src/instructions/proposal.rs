@@ -1,11 +1,14 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, Mint, TokenAccount};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::error::ErrorCode;
+use crate::constants::days_to_seconds;
 use crate::instructions::admin::GLOBAL_ACCOUNT_SEED;
+use crate::instructions::staking::received_amount;
 
 pub const PROPOSAL_SEED: &[u8] = b"proposal";
 pub const PROPOSAL_ESCROW_SEED: &[u8] = b"proposal_escrow";
+pub const MAX_VOTING_PERIOD_DAYS: i64 = 90;
 
 ////////////////////////////////////////////////////////////////
 //                     PROPOSAL CONTEXTS
@@ -23,8 +26,9 @@ pub struct CreateProposal<'info> {
         init,
         payer = author,
         // Space: discriminator(8) + number(8) + author(32) + title(4+100) + question(4+500) + yes(8) + no(8) + deadline(8) + is_active(1) 
-        // + proposal_type(1) + transfer_amount(8) + transfer_destination(32) + timelock_seconds(8) + executed(1) = 731 bytes
-        space = 8 + 8 + 32 + 104 + 504 + 8 + 8 + 8 + 1 + 1 + 8 + 32 + 8 + 1,
+        // + proposal_type(1) + transfer_amount(8) + transfer_destination(32) + timelock_seconds(8) + executed(1) + vetoed(1)
+        // + emergency(1) = 733 bytes
+        space = 8 + 8 + 32 + 104 + 504 + 8 + 8 + 8 + 1 + 1 + 8 + 32 + 8 + 1 + 1 + 1,
         seeds = [PROPOSAL_SEED, (global_account.proposal_count + 1).to_le_bytes().as_ref()],
         bump
     )]
@@ -47,8 +51,9 @@ pub struct CreateTreasuryProposal<'info> {
         init,
         payer = author,
         // Space: discriminator(8) + number(8) + author(32) + title(4+100) + question(4+500) + yes(8) + no(8) + deadline(8) + is_active(1) 
-        // + proposal_type(1) + transfer_amount(8) + transfer_destination(32) + timelock_seconds(8) + executed(1) = 731 bytes
-        space = 8 + 8 + 32 + 104 + 504 + 8 + 8 + 8 + 1 + 1 + 8 + 32 + 8 + 1,
+        // + proposal_type(1) + transfer_amount(8) + transfer_destination(32) + timelock_seconds(8) + executed(1) + vetoed(1)
+        // + emergency(1) = 733 bytes
+        space = 8 + 8 + 32 + 104 + 504 + 8 + 8 + 8 + 1 + 1 + 8 + 32 + 8 + 1 + 1 + 1,
         seeds = [PROPOSAL_SEED, (global_account.proposal_count + 1).to_le_bytes().as_ref()],
         bump
     )]
@@ -62,23 +67,23 @@ pub struct CreateTreasuryProposal<'info> {
         token::mint = token_mint,
         token::authority = proposal_escrow,
     )]
-    pub proposal_escrow: Account<'info, TokenAccount>,
+    pub proposal_escrow: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         constraint = token_mint.key() == global_account.token_mint
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         constraint = author_token_account.mint == token_mint.key(),
         constraint = author_token_account.owner == author.key()
     )]
-    pub author_token_account: Account<'info, TokenAccount>,
+    pub author_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub author: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -92,9 +97,11 @@ pub fn create_proposal(
     title: String,
     description: String,
     deadline: i64,
+    emergency: bool,
 ) -> Result<()> {
     let global_account = &mut ctx.accounts.global_account;
     require!(global_account.system_enabled, ErrorCode::CircuitBreakerTripped);
+    require_valid_deadline(deadline)?;
 
     let proposal_account = &mut ctx.accounts.proposal_account;
 
@@ -114,11 +121,14 @@ pub fn create_proposal(
     proposal_account.transfer_destination = ctx.accounts.author.key(); // Placeholder
     proposal_account.timelock_seconds = 0;
     proposal_account.executed = false;
+    proposal_account.vetoed = false;
+    proposal_account.emergency = emergency;
 
     Ok(())
 }
 
 /// Create a treasury proposal with tokens deposited to escrow
+#[allow(clippy::too_many_arguments)]
 pub fn create_treasury_proposal(
     ctx: Context<CreateTreasuryProposal>,
     title: String,
@@ -127,24 +137,32 @@ pub fn create_treasury_proposal(
     transfer_amount: u64,
     transfer_destination: Pubkey,
     timelock_seconds: i64,
+    emergency: bool,
 ) -> Result<()> {
     let global_account = &mut ctx.accounts.global_account;
     require!(global_account.system_enabled, ErrorCode::CircuitBreakerTripped);
     require!(transfer_amount > 0, ErrorCode::InvalidAmount);
+    require_valid_deadline(deadline)?;
 
     // Transfer tokens from author to escrow
-    anchor_spl::token::transfer(
+    let escrow_balance_before = ctx.accounts.proposal_escrow.amount;
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Transfer {
+            token_interface::TransferChecked {
                 from: ctx.accounts.author_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.proposal_escrow.to_account_info(),
                 authority: ctx.accounts.author.to_account_info(),
             },
         ),
         transfer_amount,
+        ctx.accounts.token_mint.decimals,
     )?;
 
+    // Escrow what arrived, so execution never pays out more than the escrow holds
+    let escrowed_amount = received_amount(&mut ctx.accounts.proposal_escrow, escrow_balance_before)?;
+
     global_account.proposal_count += 1;
 
     let proposal_account = &mut ctx.accounts.proposal_account;
@@ -157,10 +175,22 @@ pub fn create_treasury_proposal(
     proposal_account.deadline = deadline;
     proposal_account.is_active = true;
     proposal_account.proposal_type = 1; // TreasuryTransfer
-    proposal_account.transfer_amount = transfer_amount;
+    proposal_account.transfer_amount = escrowed_amount;
     proposal_account.transfer_destination = transfer_destination;
     proposal_account.timelock_seconds = timelock_seconds;
     proposal_account.executed = false;
+    proposal_account.vetoed = false;
+    proposal_account.emergency = emergency;
+
+    Ok(())
+}
 
+/// Voting must end in the future and within `MAX_VOTING_PERIOD_DAYS`.
+pub fn require_valid_deadline(deadline: i64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        deadline > now && deadline <= now + days_to_seconds(MAX_VOTING_PERIOD_DAYS),
+        ErrorCode::InvalidDeadline
+    );
     Ok(())
 }
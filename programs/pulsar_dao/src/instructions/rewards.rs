@@ -0,0 +1,715 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::instructions::admin::{DAO_CONFIG_SEED, GLOBAL_ACCOUNT_SEED, MAX_BPS, TREASURY_VAULT_SEED, load_dao_config};
+use crate::instructions::delegation::{DELEGATE_PROFILE_SEED, DELEGATION_RECORD_SEED, effective_commission_bps, is_delegating};
+use crate::instructions::staking::{
+    STAKE_RECORD_SEED, STAKER_PROFILE_SEED, StakeSnapshot, weighted_stake, update_stake_totals, update_staker_count,
+    is_position_authority, is_position_nft_account, received_amount,
+};
+
+pub const REWARD_POOL_SEED: &[u8] = b"reward_pool";
+pub const REWARD_VAULT_SEED: &[u8] = b"reward_vault";
+
+/// Fixed-point scale of `RewardPool::acc_reward_per_share`.
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+// Space: 8 (discriminator) + 8 (reward_rate) + 16 (acc_reward_per_share) + 8 (last_update_time) + 8 (total_weighted_stake)
+pub const REWARD_POOL_SPACE: usize = 8 + 8 + 16 + 8 + 8;
+
+////////////////////////////////////////////////////////////////
+//                      REWARD CONTEXTS
+////////////////////////////////////////////////////////////////
+
+/// The pool itself may already exist: staking instructions create it
+/// lazily (at a zero rate) so they never depend on this call.
+#[derive(Accounts)]
+pub struct InitializeRewardPool<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = REWARD_POOL_SPACE,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [REWARD_VAULT_SEED, token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = reward_vault,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == global_account.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetRewardRate<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Moves tokens from the DAO treasury vault into the reward vault.
+#[derive(Accounts)]
+pub struct FundRewardPool<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == global_account.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub admin: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// When the position's owner delegates, each delegate's
+/// `(delegate_profile, delegate_token_account)` pair is passed through
+/// `remaining_accounts`, in share order, to receive their commission.
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct ClaimRewards<'info> {
+    /// CHECK: The DAO config, if initialized. Defaults apply until then (see `load_dao_config`).
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_record.owner.as_ref(), position_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = is_position_authority(&stake_record, &user.key(), &position_token_account) @ ErrorCode::Unauthorized,
+    )]
+    pub stake_record: Account<'info, VoterStakeRecord>,
+
+    /// Holder's token account for the position NFT. Required once the position is tokenized.
+    pub position_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_mint.key()
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: The position owner's delegation record, if any. Its delegates earn commission.
+    #[account(
+        seeds = [DELEGATION_RECORD_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub delegation_record: UncheckedAccount<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub user: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Delegate commissions are paid as in `ClaimRewards`.
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct CompoundRewards<'info> {
+    /// CHECK: The DAO config, if initialized. Defaults apply until then (see `load_dao_config`).
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKER_PROFILE_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub staker_profile: Account<'info, StakerProfile>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_record.owner.as_ref(), position_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = is_position_authority(&stake_record, &user.key(), &position_token_account) @ ErrorCode::Unauthorized,
+    )]
+    pub stake_record: Account<'info, VoterStakeRecord>,
+
+    /// Holder's token account for the position NFT. Required once the position is tokenized.
+    pub position_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: The position owner's delegation record, if any. Its delegates earn commission.
+    #[account(
+        seeds = [DELEGATION_RECORD_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub delegation_record: UncheckedAccount<'info>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub user: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+////////////////////////////////////////////////////////////////
+//                      REWARD HANDLERS
+////////////////////////////////////////////////////////////////
+
+pub fn initialize_reward_pool(ctx: Context<InitializeRewardPool>, reward_rate: u64) -> Result<()> {
+    require!(
+        ctx.accounts.global_account.admin == ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    // Keep the weight of positions registered since the pool was created
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    update_reward_pool(reward_pool, Clock::get()?.unix_timestamp);
+    reward_pool.reward_rate = reward_rate;
+
+    Ok(())
+}
+
+/// Change the emission rate. Rewards up to now accrue at the old rate.
+pub fn set_reward_rate(ctx: Context<SetRewardRate>, reward_rate: u64) -> Result<()> {
+    require!(
+        ctx.accounts.global_account.admin == ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    update_reward_pool(reward_pool, Clock::get()?.unix_timestamp);
+    reward_pool.reward_rate = reward_rate;
+
+    Ok(())
+}
+
+pub fn fund_reward_pool(ctx: Context<FundRewardPool>, amount: u64) -> Result<()> {
+    require!(
+        ctx.accounts.global_account.admin == ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.treasury_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.reward_vault.to_account_info(),
+                authority: ctx.accounts.treasury_vault.to_account_info(),
+            },
+            &[&[
+                TREASURY_VAULT_SEED,
+                ctx.accounts.token_mint.key().as_ref(),
+                &[ctx.bumps.treasury_vault],
+            ]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    Ok(())
+}
+
+/// Pay out a position's accrued rewards, and the commission set aside for
+/// the owner's delegates. If the reward vault runs short, the unpaid part
+/// stays pending on the position.
+pub fn claim_rewards<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimRewards<'info>>,
+    _position_id: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let commission_bps = position_commission_bps(
+        &ctx.accounts.stake_record,
+        &ctx.accounts.user.key(),
+        &ctx.accounts.delegation_record,
+        ctx.program_id,
+    )?;
+    accrue_position_rewards(
+        &mut ctx.accounts.reward_pool,
+        &mut ctx.accounts.stake_record,
+        commission_bps,
+        now,
+    );
+    let commission = pay_position_commission(
+        &mut ctx.accounts.stake_record,
+        &ctx.accounts.delegation_record,
+        ctx.remaining_accounts,
+        load_dao_config(&ctx.accounts.dao_config, ctx.program_id)?.max_delegate_commission_bps,
+        ctx.accounts.reward_vault.amount,
+        true,
+        &RewardVault {
+            reward_vault: &ctx.accounts.reward_vault,
+            bump: ctx.bumps.reward_vault,
+            token_mint: &ctx.accounts.token_mint,
+            token_program: &ctx.accounts.token_program,
+        },
+        ctx.program_id,
+    )?;
+    let available = ctx.accounts.reward_vault.amount.checked_sub(commission).unwrap();
+    let amount = take_pending_rewards(&mut ctx.accounts.stake_record, available);
+    require!(amount > 0 || commission > 0, ErrorCode::NoRewardsToClaim);
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.reward_vault.to_account_info(),
+            },
+            &[&[
+                REWARD_VAULT_SEED,
+                ctx.accounts.token_mint.key().as_ref(),
+                &[ctx.bumps.reward_vault],
+            ]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let stake_record = &ctx.accounts.stake_record;
+    emit!(RewardsClaimed {
+        owner: stake_record.owner,
+        position_id: stake_record.position_id,
+        amount,
+        compounded: false,
+    });
+
+    Ok(())
+}
+
+/// Restake a position's accrued rewards into the same position. The lock
+/// and multiplier are unchanged.
+pub fn compound_rewards<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CompoundRewards<'info>>,
+    _position_id: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let commission_bps = position_commission_bps(
+        &ctx.accounts.stake_record,
+        &ctx.accounts.user.key(),
+        &ctx.accounts.delegation_record,
+        ctx.program_id,
+    )?;
+    accrue_position_rewards(
+        &mut ctx.accounts.reward_pool,
+        &mut ctx.accounts.stake_record,
+        commission_bps,
+        now,
+    );
+    let commission = pay_position_commission(
+        &mut ctx.accounts.stake_record,
+        &ctx.accounts.delegation_record,
+        ctx.remaining_accounts,
+        load_dao_config(&ctx.accounts.dao_config, ctx.program_id)?.max_delegate_commission_bps,
+        ctx.accounts.reward_vault.amount,
+        true,
+        &RewardVault {
+            reward_vault: &ctx.accounts.reward_vault,
+            bump: ctx.bumps.reward_vault,
+            token_mint: &ctx.accounts.token_mint,
+            token_program: &ctx.accounts.token_program,
+        },
+        ctx.program_id,
+    )?;
+    let available = ctx.accounts.reward_vault.amount.checked_sub(commission).unwrap();
+    let amount = take_pending_rewards(&mut ctx.accounts.stake_record, available);
+    require!(amount > 0 || commission > 0, ErrorCode::NoRewardsToClaim);
+
+    let vault_balance_before = ctx.accounts.vault.amount;
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.reward_vault.to_account_info(),
+            },
+            &[&[
+                REWARD_VAULT_SEED,
+                ctx.accounts.token_mint.key().as_ref(),
+                &[ctx.bumps.reward_vault],
+            ]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    let received = received_amount(&mut ctx.accounts.vault, vault_balance_before)?;
+
+    let stake_record = &mut ctx.accounts.stake_record;
+    let before = StakeSnapshot::of(stake_record);
+    stake_record.staked_amount = stake_record.staked_amount.checked_add(received).unwrap();
+    reweight_position(&mut ctx.accounts.reward_pool, stake_record, now);
+    update_stake_totals(&mut ctx.accounts.global_account, before, stake_record);
+    update_staker_count(&mut ctx.accounts.global_account, &mut ctx.accounts.staker_profile, before, stake_record);
+
+    emit!(RewardsClaimed {
+        owner: stake_record.owner,
+        position_id: stake_record.position_id,
+        amount,
+        compounded: true,
+    });
+
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////
+//                      REWARD HELPERS
+////////////////////////////////////////////////////////////////
+
+/// Bring the accumulator up to `now`. Nothing accrues while no stake is weighted.
+pub fn update_reward_pool(reward_pool: &mut RewardPool, now: i64) {
+    if now <= reward_pool.last_update_time {
+        return;
+    }
+
+    if reward_pool.total_weighted_stake > 0 {
+        let elapsed = (now - reward_pool.last_update_time) as u128;
+        let emitted = elapsed.checked_mul(reward_pool.reward_rate as u128).unwrap();
+        reward_pool.acc_reward_per_share = reward_pool
+            .acc_reward_per_share
+            .checked_add(emitted * REWARD_PRECISION / reward_pool.total_weighted_stake as u128)
+            .unwrap();
+    }
+    reward_pool.last_update_time = now;
+}
+
+/// Move a position's rewards earned so far into `pending_rewards`, less
+/// `commission_bps` for the owner's delegates, which is set aside in
+/// `pending_commission`. Call before changing the position's stake or
+/// multiplier.
+pub fn accrue_position_rewards(
+    reward_pool: &mut RewardPool,
+    stake_record: &mut VoterStakeRecord,
+    commission_bps: u16,
+    now: i64,
+) {
+    update_reward_pool(reward_pool, now);
+
+    let accrued = stake_record.reward_weight as u128 * reward_pool.acc_reward_per_share / REWARD_PRECISION;
+    let earned = accrued.saturating_sub(stake_record.reward_debt) as u64;
+    let commission = (earned as u128 * commission_bps as u128 / MAX_BPS as u128) as u64;
+    stake_record.pending_commission = stake_record.pending_commission.checked_add(commission).unwrap();
+    stake_record.pending_rewards = stake_record.pending_rewards.checked_add(earned - commission).unwrap();
+    stake_record.reward_debt = accrued;
+}
+
+/// The commission charged on rewards as they accrue to positions opened by
+/// the owner of `delegation_record_info`: the record's rate while the
+/// delegation is live, otherwise nothing.
+pub fn delegation_commission_bps(delegation_record_info: &AccountInfo, program_id: &Pubkey) -> Result<u16> {
+    if !is_delegating(delegation_record_info, program_id)? {
+        return Ok(0);
+    }
+    let delegation_record = DelegationRecord::try_deserialize(&mut &delegation_record_info.try_borrow_data()?[..])?;
+    Ok(delegation_record.commission_bps)
+}
+
+/// The commission charged on a position's rewards as they accrue: that of
+/// its owner's delegation while `holder`, the wallet acting on it, is the
+/// owner. Once its NFT has been sold the owner's delegates earn nothing more
+/// from it.
+pub fn position_commission_bps(
+    stake_record: &VoterStakeRecord,
+    holder: &Pubkey,
+    delegation_record_info: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<u16> {
+    if stake_record.owner != *holder {
+        return Ok(0);
+    }
+    delegation_commission_bps(delegation_record_info, program_id)
+}
+
+/// Accrue every position `owner` opened, in position id order, before their
+/// delegation changes, so rewards earned up to now are charged at the
+/// commission of the delegation they were earned under. Token accounts
+/// between the positions (NFTs of tokenized ones) are skipped; a tokenized
+/// position not followed by the owner's token account for its NFT has been
+/// sold and accrues without commission. Nothing accrues before the reward
+/// pool exists.
+pub fn accrue_owner_positions(
+    accounts: &[AccountInfo],
+    owner: &Pubkey,
+    position_count: u64,
+    reward_pool_info: &AccountInfo,
+    commission_bps: u16,
+    program_id: &Pubkey,
+) -> Result<()> {
+    let mut reward_pool = if !reward_pool_info.data_is_empty() && reward_pool_info.owner == program_id {
+        let (expected, _) = Pubkey::find_program_address(&[REWARD_POOL_SEED], program_id);
+        require_keys_eq!(*reward_pool_info.key, expected, ErrorCode::InvalidConfig);
+        Some(RewardPool::try_deserialize(&mut &reward_pool_info.try_borrow_data()?[..])?)
+    } else {
+        None
+    };
+    let now = Clock::get()?.unix_timestamp;
+
+    let mut accounts = accounts.iter().peekable();
+    for position_id in 0..position_count {
+        let info = accounts
+            .by_ref()
+            .find(|info| info.owner == program_id)
+            .ok_or(ErrorCode::MissingStakePosition)?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[STAKE_RECORD_SEED, owner.as_ref(), position_id.to_le_bytes().as_ref()],
+            program_id,
+        );
+        require_keys_eq!(*info.key, expected, ErrorCode::MissingStakePosition);
+
+        if let Some(reward_pool) = reward_pool.as_mut() {
+            require!(info.is_writable, ErrorCode::InvalidStakePosition);
+            let mut stake_record = VoterStakeRecord::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            let held = stake_record.position_mint == Pubkey::default()
+                || match accounts.peek() {
+                    Some(next) => is_position_nft_account(next, &stake_record.position_mint, owner)?,
+                    None => false,
+                };
+            let commission_bps = if held { commission_bps } else { 0 };
+            accrue_position_rewards(reward_pool, &mut stake_record, commission_bps, now);
+            stake_record.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        }
+    }
+
+    if let Some(reward_pool) = reward_pool {
+        reward_pool.try_serialize(&mut &mut reward_pool_info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+/// Re-register a position's weight with the pool after its stake or
+/// multiplier changed. Must follow `accrue_position_rewards`.
+pub fn reweight_position(reward_pool: &mut RewardPool, stake_record: &mut VoterStakeRecord, now: i64) {
+    let weight = weighted_stake(stake_record, now);
+
+    reward_pool.total_weighted_stake = reward_pool
+        .total_weighted_stake
+        .saturating_sub(stake_record.reward_weight)
+        .checked_add(weight)
+        .unwrap();
+    stake_record.reward_weight = weight;
+    stake_record.reward_debt = weight as u128 * reward_pool.acc_reward_per_share / REWARD_PRECISION;
+}
+
+/// Take up to `available` of a position's pending rewards.
+fn take_pending_rewards(stake_record: &mut VoterStakeRecord, available: u64) -> u64 {
+    let amount = stake_record.pending_rewards.min(available);
+    stake_record.pending_rewards -= amount;
+    amount
+}
+
+/// The reward vault and what is needed to pay out of it.
+pub struct RewardVault<'a, 'info> {
+    pub reward_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub bump: u8,
+    pub token_mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Pay a position's `pending_commission` to the delegates of its owner's
+/// delegation record, passed as `(delegate_profile, delegate_token_account)`
+/// pairs in share order. Each delegate gets their share at their current
+/// commission capped by `max_commission_bps`, so a cut or a lower cap since
+/// the commission was charged pays less; the difference goes back to the
+/// position's rewards, as does all of it once the delegation is voided or
+/// the delegate removed. A raise does not pay more: the charged commission
+/// is then split pro rata by share and current rate, so no delegate takes
+/// another's part. At most `available` is paid. With `keep_unpaid`
+/// the shortfall stays owed, otherwise it goes back to the rewards too.
+/// Returns the amount paid.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_position_commission<'info>(
+    stake_record: &mut VoterStakeRecord,
+    delegation_record_info: &AccountInfo<'info>,
+    commission_accounts: &'info [AccountInfo<'info>],
+    max_commission_bps: u16,
+    available: u64,
+    keep_unpaid: bool,
+    vault: &RewardVault<'_, 'info>,
+    program_id: &Pubkey,
+) -> Result<u64> {
+    let owed = stake_record.pending_commission;
+    if owed == 0 {
+        return Ok(0);
+    }
+    stake_record.pending_commission = 0;
+
+    let delegation_record = if !delegation_record_info.data_is_empty() && delegation_record_info.owner == program_id {
+        Some(DelegationRecord::try_deserialize(&mut &delegation_record_info.try_borrow_data()?[..])?)
+    } else {
+        None
+    };
+    let delegation_record = match delegation_record {
+        Some(record) if !record.shares.is_empty() && record.commission_bps > 0 => record,
+        _ => {
+            stake_record.pending_rewards = stake_record.pending_rewards.checked_add(owed).unwrap();
+            return Ok(0);
+        }
+    };
+    require!(
+        commission_accounts.len() == delegation_record.shares.len() * 2,
+        ErrorCode::InvalidDelegate
+    );
+
+    let payable = owed.min(available);
+    let now = Clock::get()?.unix_timestamp;
+    let mint_key = vault.token_mint.key();
+
+    // Each delegate's weight is its share at its current rate. A removed
+    // delegate earns nothing
+    let mut weights: Vec<u128> = Vec::with_capacity(delegation_record.shares.len());
+    for (share, pair) in delegation_record.shares.iter().zip(commission_accounts.chunks(2)) {
+        let profile_info = &pair[0];
+        let (expected, _) = Pubkey::find_program_address(&[DELEGATE_PROFILE_SEED, share.delegate.as_ref()], program_id);
+        require_keys_eq!(profile_info.key(), expected, ErrorCode::InvalidDelegate);
+        if profile_info.data_is_empty() {
+            weights.push(0);
+            continue;
+        }
+        let delegate_profile = Account::<DelegateProfile>::try_from(profile_info)?;
+        let commission_bps = effective_commission_bps(&delegate_profile, now).min(max_commission_bps);
+        weights.push(share.bps as u128 * commission_bps as u128);
+    }
+    // Rates cut since delegating leave part of `payable` unpaid; rates
+    // raised since only change how it is split, never how much is paid
+    let charged_weight = MAX_BPS as u128 * delegation_record.commission_bps as u128;
+    let total_weight = weights.iter().sum::<u128>().max(charged_weight);
+    let mut paid: u64 = 0;
+
+    for ((share, pair), weight) in delegation_record.shares.iter().zip(commission_accounts.chunks(2)).zip(weights) {
+        let delegate_token_info = &pair[1];
+        let commission = (payable as u128 * weight / total_weight) as u64;
+        if commission == 0 {
+            continue;
+        }
+
+        let delegate_token_account = InterfaceAccount::<TokenAccount>::try_from(delegate_token_info)?;
+        require!(
+            delegate_token_account.mint == mint_key && delegate_token_account.owner == share.delegate,
+            ErrorCode::InvalidTokenAccount
+        );
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                vault.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: vault.reward_vault.to_account_info(),
+                    mint: vault.token_mint.to_account_info(),
+                    to: delegate_token_info.clone(),
+                    authority: vault.reward_vault.to_account_info(),
+                },
+                &[&[REWARD_VAULT_SEED, mint_key.as_ref(), &[vault.bump]]],
+            ),
+            commission,
+            vault.token_mint.decimals,
+        )?;
+        paid = paid.checked_add(commission).unwrap();
+
+        emit!(DelegateCommissionPaid {
+            delegate: share.delegate,
+            delegator: delegation_record.delegator,
+            amount: commission,
+        });
+    }
+
+    let unpaid = owed - payable;
+    let returned = payable - paid;
+    if keep_unpaid {
+        stake_record.pending_commission = unpaid;
+        stake_record.pending_rewards = stake_record.pending_rewards.checked_add(returned).unwrap();
+    } else {
+        stake_record.pending_rewards = stake_record.pending_rewards.checked_add(returned + unpaid).unwrap();
+    }
+    Ok(paid)
+}
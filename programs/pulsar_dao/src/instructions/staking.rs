@@ -1,20 +1,45 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, Mint, TokenAccount, self};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::spl_token_2022::instruction::AuthorityType;
 use crate::state::*;
 use crate::error::ErrorCode;
+use crate::constants::{SECONDS_PER_DAY, days_to_seconds};
+use crate::instructions::admin::{GLOBAL_ACCOUNT_SEED, DAO_CONFIG_SEED, TREASURY_VAULT_SEED, MAX_BPS, load_dao_config};
+use crate::instructions::delegation::DELEGATION_RECORD_SEED;
+use crate::instructions::rewards::{
+    REWARD_POOL_SEED, REWARD_POOL_SPACE, accrue_position_rewards, position_commission_bps, reweight_position,
+};
+
+pub const STAKER_PROFILE_SEED: &[u8] = b"staker_profile";
+pub const STAKE_RECORD_SEED: &[u8] = b"stake_record";
+pub const POSITION_MINT_SEED: &[u8] = b"position_mint";
 
 ////////////////////////////////////////////////////////////////
 //                      STAKING CONTEXTS
 ////////////////////////////////////////////////////////////////
 
+/// Opens a new stake position at the user's next position index.
 #[derive(Accounts)]
 pub struct InitializeStake<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        // Space: 8 (discriminator) + 32 (owner) + 8 (position_count) + 8 (active_positions)
+        space = 8 + 32 + 8 + 8,
+        seeds = [STAKER_PROFILE_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub staker_profile: Account<'info, StakerProfile>,
+
     #[account(
         init,
         payer = user,
-        // Space: 8 (discriminator) + 32 (owner) + 8 (staked_amount) + 8 (lock_end_time) + 8 (original_lock_days) + 8 (multiplier)
-        space = 8 + 32 + 8 + 8 + 8 + 8,
-        seeds = [b"stake_record", user.key().as_ref()],
+        // Space: 8 (discriminator) + 32 (owner) + 8 (position_id) + 8 (staked_amount) + 8 (lock_end_time) + 8 (original_lock_days) + 8 (multiplier) + 8 (unbonding_amount) + 8 (unbonding_end_time)
+        //        + 8 (reward_weight) + 16 (reward_debt) + 8 (pending_rewards) + 32 (position_mint) + 32 (vote_holder) + 8 (vote_reserved_until)
+        //        + 8 (pending_commission)
+        space = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 16 + 8 + 32 + 32 + 8 + 8,
+        seeds = [STAKE_RECORD_SEED, user.key().as_ref(), staker_profile.position_count.to_le_bytes().as_ref()],
         bump
     )]
     pub stake_record: Account<'info, VoterStakeRecord>,
@@ -24,17 +49,49 @@ pub struct InitializeStake<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(position_id: u64)]
 pub struct DepositTokens<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
     pub global_account: Account<'info, GlobalAccount>,
-    
+
     #[account(
         mut,
-        seeds = [b"stake_record", user.key().as_ref()],
+        seeds = [STAKER_PROFILE_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub staker_profile: Account<'info, StakerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = REWARD_POOL_SPACE,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_record.owner.as_ref(), position_id.to_le_bytes().as_ref()],
         bump,
-        constraint = stake_record.owner == user.key(),
+        constraint = is_position_authority(&stake_record, &user.key(), &position_token_account) @ ErrorCode::Unauthorized,
     )]
     pub stake_record: Account<'info, VoterStakeRecord>,
 
+    /// Holder's token account for the position NFT. Required once the position is tokenized.
+    pub position_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The position owner's delegation record, if any. Sets the commission charged as rewards accrue.
+    #[account(
+        seeds = [DELEGATION_RECORD_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub delegation_record: UncheckedAccount<'info>,
+
     #[account(
         init_if_needed,
         payer = user,
@@ -43,109 +100,771 @@ pub struct DepositTokens<'info> {
         token::mint = token_mint,
         token::authority = vault, 
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
     
-    pub token_mint: Account<'info, Mint>,
+    #[account(
+        constraint = token_mint.key() == global_account.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub user: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct UnstakeTokens<'info> {
+#[instruction(position_id: u64)]
+pub struct RequestUnstake<'info> {
+    /// CHECK: The DAO config, if initialized. Defaults apply until then (see `load_dao_config`).
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
     #[account(
         mut,
-        seeds = [b"stake_record", user.key().as_ref()],
+        seeds = [STAKER_PROFILE_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub staker_profile: Account<'info, StakerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = REWARD_POOL_SPACE,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_record.owner.as_ref(), position_id.to_le_bytes().as_ref()],
         bump,
-        constraint = stake_record.owner == user.key(),
+        constraint = is_position_authority(&stake_record, &user.key(), &position_token_account) @ ErrorCode::Unauthorized,
+    )]
+    pub stake_record: Account<'info, VoterStakeRecord>,
+
+    /// Holder's token account for the position NFT. Required once the position is tokenized.
+    pub position_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The position owner's delegation record, if any. Sets the commission charged as rewards accrue.
+    #[account(
+        seeds = [DELEGATION_RECORD_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub delegation_record: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct WithdrawUnstaked<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_record.owner.as_ref(), position_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = is_position_authority(&stake_record, &user.key(), &position_token_account) @ ErrorCode::Unauthorized,
     )]
     pub stake_record: Account<'info, VoterStakeRecord>,
 
+    /// Holder's token account for the position NFT. Required once the position is tokenized.
+    pub position_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
     #[account(
         mut,
         seeds = [b"vault", token_mint.key().as_ref()],
         bump
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    pub token_mint: Account<'info, Mint>,
+    #[account(
+        constraint = token_mint.key() == global_account.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
     #[account(mut)]
     pub user: Signer<'info>, 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct ExtendLock<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = REWARD_POOL_SPACE,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_record.owner.as_ref(), position_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = is_position_authority(&stake_record, &user.key(), &position_token_account) @ ErrorCode::Unauthorized,
+    )]
+    pub stake_record: Account<'info, VoterStakeRecord>,
+
+    /// Holder's token account for the position NFT. Required once the position is tokenized.
+    pub position_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The position owner's delegation record, if any. Sets the commission charged as rewards accrue.
+    #[account(
+        seeds = [DELEGATION_RECORD_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub delegation_record: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless: anyone may settle a position whose lock has ended.
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct ExpireLock<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = REWARD_POOL_SPACE,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_record.owner.as_ref(), position_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub stake_record: Account<'info, VoterStakeRecord>,
+
+    /// Current holder's token account for the position NFT. Required once the position is tokenized.
+    pub position_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The position owner's delegation record, if any. Sets the commission charged as rewards accrue.
+    #[account(
+        seeds = [DELEGATION_RECORD_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub delegation_record: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct EarlyUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    /// CHECK: The DAO config, if initialized. Defaults apply until then (see `load_dao_config`).
+    #[account(
+        seeds = [DAO_CONFIG_SEED],
+        bump
+    )]
+    pub dao_config: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [STAKER_PROFILE_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub staker_profile: Account<'info, StakerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = REWARD_POOL_SPACE,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_record.owner.as_ref(), position_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = is_position_authority(&stake_record, &user.key(), &position_token_account) @ ErrorCode::Unauthorized,
+    )]
+    pub stake_record: Account<'info, VoterStakeRecord>,
+
+    /// Holder's token account for the position NFT. Required once the position is tokenized.
+    pub position_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The position owner's delegation record, if any. Sets the commission charged as rewards accrue.
+    #[account(
+        seeds = [DELEGATION_RECORD_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub delegation_record: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the penalty unless it is burned. Created by `initialize_config`.
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub treasury_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == global_account.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    /// CHECK: The owner's staker profile. Updated only if it loads, so the
+    /// exit never depends on it.
+    #[account(
+        mut,
+        seeds = [STAKER_PROFILE_SEED, stake_record.owner.as_ref()],
+        bump
+    )]
+    pub staker_profile: UncheckedAccount<'info>,
+
+    /// CHECK: The reward pool. Updated only if it loads, so the exit never
+    /// depends on it.
+    #[account(
+        mut,
+        seeds = [REWARD_POOL_SEED],
+        bump
+    )]
+    pub reward_pool: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_record.owner.as_ref(), position_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = is_position_authority(&stake_record, &user.key(), &position_token_account) @ ErrorCode::Unauthorized,
+    )]
+    pub stake_record: Account<'info, VoterStakeRecord>,
+
+    /// Holder's token account for the position NFT. Required once the position is tokenized.
+    pub position_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == token_mint.key()
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_mint.key() == global_account.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub user: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Mints a one-of-one NFT for a position. Whoever holds it controls the
+/// position from then on.
+#[derive(Accounts)]
+#[instruction(position_id: u64)]
+pub struct TokenizePosition<'info> {
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, user.key().as_ref(), position_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = stake_record.owner == user.key(),
+    )]
+    pub stake_record: Account<'info, VoterStakeRecord>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [POSITION_MINT_SEED, stake_record.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = position_mint,
+    )]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = position_mint,
+        associated_token::authority = user
+    )]
+    pub position_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
 ////////////////////////////////////////////////////////////////
 //                      STAKING HANDLERS
 ////////////////////////////////////////////////////////////////
 
 pub fn initialize_stake(ctx: Context<InitializeStake>) -> Result<()> {
+    let staker_profile = &mut ctx.accounts.staker_profile;
+    staker_profile.owner = ctx.accounts.user.key();
+
     let stake_record = &mut ctx.accounts.stake_record;
     stake_record.owner = ctx.accounts.user.key();
+    stake_record.position_id = staker_profile.position_count;
     stake_record.staked_amount = 0;
-    stake_record.multiplier = 1;
+    stake_record.multiplier = MULTIPLIER_BPS;
+
+    staker_profile.position_count = staker_profile.position_count.checked_add(1).unwrap();
     Ok(())
 }
 
-pub fn deposit_tokens(ctx: Context<DepositTokens>, amount: u64, lock_days: i64) -> Result<()> {
+pub fn deposit_tokens(ctx: Context<DepositTokens>, _position_id: u64, amount: u64, lock_days: i64) -> Result<()> {
     let stake_record = &mut ctx.accounts.stake_record;
 
-    token::transfer(
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+    let multiplier = multiplier_for_lock(lock_days)?;
+    let lock_end_time = lock_end_time(current_time, lock_days)?;
+
+    // Security: A top-up may not shorten a running lock or lower its multiplier
+    if stake_record.staked_amount > 0 && current_time < stake_record.lock_end_time {
+        require!(
+            lock_end_time >= stake_record.lock_end_time && multiplier >= stake_record.multiplier,
+            ErrorCode::LockDurationDowngrade
+        );
+    }
+
+    let vault_balance_before = ctx.accounts.vault.amount;
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
+            token_interface::TransferChecked {
                 from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.vault.to_account_info(),
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
         amount,
+        ctx.accounts.token_mint.decimals,
     )?;
+    let received = received_amount(&mut ctx.accounts.vault, vault_balance_before)?;
+
+    let before = StakeSnapshot::of(stake_record);
+    let commission_bps = position_commission_bps(stake_record, &ctx.accounts.user.key(), &ctx.accounts.delegation_record, ctx.program_id)?;
+    accrue_position_rewards(&mut ctx.accounts.reward_pool, stake_record, commission_bps, current_time);
+    stake_record.staked_amount = stake_record.staked_amount.checked_add(received).unwrap();
+    stake_record.lock_end_time = lock_end_time;
+    stake_record.original_lock_days = lock_days;
+    stake_record.multiplier = multiplier;
+    reweight_position(&mut ctx.accounts.reward_pool, stake_record, current_time);
+    update_stake_totals(&mut ctx.accounts.global_account, before, stake_record);
+    update_staker_count(&mut ctx.accounts.global_account, &mut ctx.accounts.staker_profile, before, stake_record);
+
+    Ok(())
+}
+
+/// Exit a position before its lock ends. The penalty is the configured
+/// rate scaled by the share of the lock still remaining, and goes to the
+/// treasury vault (or is burned). The rest of the position starts unbonding.
+pub fn early_unstake(ctx: Context<EarlyUnstake>, _position_id: u64) -> Result<()> {
+    let dao_config = load_dao_config(&ctx.accounts.dao_config, ctx.program_id)?;
+    let stake_record = &mut ctx.accounts.stake_record;
+    require!(stake_record.staked_amount > 0, ErrorCode::NoTokensToUnstake);
 
-    stake_record.staked_amount = stake_record.staked_amount.checked_add(amount).unwrap();
-    
     let clock = Clock::get()?;
-    let current_time = clock.unix_timestamp;
-    let lock_seconds = lock_days; // HACKATHON MODE: treated as seconds
-    
-    stake_record.lock_end_time = current_time + lock_seconds;
+    let amount = stake_record.staked_amount;
+    let penalty = early_unstake_penalty(
+        stake_record,
+        dao_config.early_unstake_penalty_bps,
+        clock.unix_timestamp,
+    );
+    let amount_unbonding = amount.checked_sub(penalty).unwrap();
+
+    let mint_key = ctx.accounts.token_mint.key();
+    let vault_seeds: &[&[u8]] = &[b"vault", mint_key.as_ref(), &[ctx.bumps.vault]];
+
+    let penalty_burned = dao_config.burn_early_unstake_penalty;
+    if penalty > 0 {
+        if penalty_burned {
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::Burn {
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        from: ctx.accounts.vault.to_account_info(),
+                        authority: ctx.accounts.vault.to_account_info(),
+                    },
+                    &[vault_seeds],
+                ),
+                penalty,
+            )?;
+        } else {
+            let treasury_vault = ctx.accounts.treasury_vault.as_ref().ok_or(ErrorCode::InvalidTokenAccount)?;
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: ctx.accounts.vault.to_account_info(),
+                        mint: ctx.accounts.token_mint.to_account_info(),
+                        to: treasury_vault.to_account_info(),
+                        authority: ctx.accounts.vault.to_account_info(),
+                    },
+                    &[vault_seeds],
+                ),
+                penalty,
+                ctx.accounts.token_mint.decimals,
+            )?;
+        }
+    }
+
+    // The position no longer carries any voting power or reward weight
+    let before = StakeSnapshot::of(stake_record);
+    let commission_bps = position_commission_bps(stake_record, &ctx.accounts.user.key(), &ctx.accounts.delegation_record, ctx.program_id)?;
+    accrue_position_rewards(&mut ctx.accounts.reward_pool, stake_record, commission_bps, clock.unix_timestamp);
+    stake_record.staked_amount = 0;
+    stake_record.multiplier = MULTIPLIER_BPS;
+    stake_record.lock_end_time = 0;
+    reweight_position(&mut ctx.accounts.reward_pool, stake_record, clock.unix_timestamp);
+    update_stake_totals(&mut ctx.accounts.global_account, before, stake_record);
+    update_staker_count(&mut ctx.accounts.global_account, &mut ctx.accounts.staker_profile, before, stake_record);
+    start_unbonding(
+        stake_record,
+        amount_unbonding,
+        dao_config.unbonding_period_days,
+        clock.unix_timestamp,
+    );
+
+    emit!(EarlyUnstaked {
+        owner: stake_record.owner,
+        position_id: stake_record.position_id,
+        amount_unbonding,
+        penalty,
+        penalty_burned,
+    });
+
+    Ok(())
+}
+
+/// Return a position's staked and unbonding tokens without any lock or
+/// unbonding checks. Only available while the breaker is tripped and the
+/// admin has enabled emergency exit. The position keeps no voting power.
+pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>, _position_id: u64) -> Result<()> {
+    let global_account = &ctx.accounts.global_account;
+    require!(
+        !global_account.system_enabled && global_account.emergency_exit_enabled,
+        ErrorCode::EmergencyExitNotActive
+    );
+
+    let stake_record = &mut ctx.accounts.stake_record;
+    let amount = stake_record.staked_amount.saturating_add(stake_record.unbonding_amount);
+    require!(amount > 0, ErrorCode::NoTokensToUnstake);
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[&[
+                b"vault",
+                ctx.accounts.token_mint.key().as_ref(),
+                &[ctx.bumps.vault],
+            ]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    // No reward accrual and only saturating bookkeeping here: nothing that
+    // can fail may stand between a staker and their tokens. Rewards earned
+    // since the position last changed are forfeited.
+    let before = StakeSnapshot::of(stake_record);
+    stake_record.staked_amount = 0;
+    stake_record.multiplier = MULTIPLIER_BPS;
+    stake_record.lock_end_time = 0;
+    stake_record.unbonding_amount = 0;
+    stake_record.unbonding_end_time = 0;
+
+    let program_id = ctx.program_id;
+    let reward_pool_info = &ctx.accounts.reward_pool;
+    if !reward_pool_info.data_is_empty() && reward_pool_info.owner == program_id {
+        let reward_pool = RewardPool::try_deserialize(&mut &reward_pool_info.try_borrow_data()?[..]);
+        if let Ok(mut reward_pool) = reward_pool {
+            reward_pool.total_weighted_stake = reward_pool.total_weighted_stake.saturating_sub(stake_record.reward_weight);
+            reward_pool.try_serialize(&mut &mut reward_pool_info.try_borrow_mut_data()?[..])?;
+        }
+    }
+    stake_record.reward_weight = 0;
+    stake_record.reward_debt = 0;
+
+    let global_account = &mut ctx.accounts.global_account;
+    global_account.total_staked = global_account.total_staked.saturating_sub(before.staked_amount);
+    global_account.total_weighted_stake = global_account.total_weighted_stake.saturating_sub(before.weighted_stake);
+
+    let staker_profile_info = &ctx.accounts.staker_profile;
+    if before.staked_amount > 0 && !staker_profile_info.data_is_empty() && staker_profile_info.owner == program_id {
+        let staker_profile = StakerProfile::try_deserialize(&mut &staker_profile_info.try_borrow_data()?[..]);
+        if let Ok(mut staker_profile) = staker_profile {
+            staker_profile.active_positions = staker_profile.active_positions.saturating_sub(1);
+            if staker_profile.active_positions == 0 {
+                global_account.staker_count = global_account.staker_count.saturating_sub(1);
+            }
+            staker_profile.try_serialize(&mut &mut staker_profile_info.try_borrow_mut_data()?[..])?;
+        }
+    }
+
+    emit!(EmergencyWithdrawn {
+        owner: stake_record.owner,
+        position_id: stake_record.position_id,
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Turn an existing position into a transferable NFT. The mint authority is
+/// dropped after minting, so the supply is fixed at one.
+pub fn tokenize_position(ctx: Context<TokenizePosition>, _position_id: u64) -> Result<()> {
+    require!(
+        ctx.accounts.stake_record.position_mint == Pubkey::default(),
+        ErrorCode::PositionAlreadyTokenized
+    );
+
+    let stake_record_key = ctx.accounts.stake_record.key();
+    let mint_seeds: &[&[u8]] = &[
+        POSITION_MINT_SEED,
+        stake_record_key.as_ref(),
+        &[ctx.bumps.position_mint],
+    ];
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: ctx.accounts.position_mint.to_account_info(),
+                to: ctx.accounts.position_token_account.to_account_info(),
+                authority: ctx.accounts.position_mint.to_account_info(),
+            },
+            &[mint_seeds],
+        ),
+        1,
+    )?;
+
+    token_interface::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::SetAuthority {
+                current_authority: ctx.accounts.position_mint.to_account_info(),
+                account_or_mint: ctx.accounts.position_mint.to_account_info(),
+            },
+            &[mint_seeds],
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    let stake_record = &mut ctx.accounts.stake_record;
+    stake_record.position_mint = ctx.accounts.position_mint.key();
+
+    emit!(PositionTokenized {
+        owner: stake_record.owner,
+        position_id: stake_record.position_id,
+        position_mint: stake_record.position_mint,
+    });
+
+    Ok(())
+}
+
+/// Re-lock an existing position without depositing. The new lock must end
+/// later than the current one and may only keep or raise the multiplier.
+pub fn extend_lock(ctx: Context<ExtendLock>, _position_id: u64, lock_days: i64) -> Result<()> {
+    let stake_record = &mut ctx.accounts.stake_record;
+    require!(stake_record.staked_amount > 0, ErrorCode::EmptyStakePosition);
+
+    let clock = Clock::get()?;
+    let multiplier = multiplier_for_lock(lock_days)?;
+    let lock_end_time = lock_end_time(clock.unix_timestamp, lock_days)?;
+
+    require!(
+        lock_end_time > stake_record.lock_end_time && multiplier >= stake_record.multiplier,
+        ErrorCode::LockDurationDowngrade
+    );
+
+    let before = StakeSnapshot::of(stake_record);
+    let commission_bps = position_commission_bps(stake_record, &ctx.accounts.user.key(), &ctx.accounts.delegation_record, ctx.program_id)?;
+    accrue_position_rewards(&mut ctx.accounts.reward_pool, stake_record, commission_bps, clock.unix_timestamp);
+    stake_record.lock_end_time = lock_end_time;
     stake_record.original_lock_days = lock_days;
+    stake_record.multiplier = multiplier;
+    reweight_position(&mut ctx.accounts.reward_pool, stake_record, clock.unix_timestamp);
+    update_stake_totals(&mut ctx.accounts.global_account, before, stake_record);
 
-    let multiplier = match lock_days {
-        30 => 2,
-        90 => 3,
-        180 => 4,
-        360 => 5,
-        _ => return Err(ErrorCode::InvalidLockDuration.into()),
+    Ok(())
+}
+
+/// Drop the reward weight of a position whose lock has ended to 1x. Until
+/// this runs or the position otherwise changes, it keeps earning at its
+/// lock multiplier.
+pub fn expire_lock(ctx: Context<ExpireLock>, _position_id: u64) -> Result<()> {
+    let stake_record = &mut ctx.accounts.stake_record;
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= stake_record.lock_end_time, ErrorCode::TokensLocked);
+
+    // Rewards up to now are charged for whoever holds the position
+    let holder = if stake_record.position_mint == Pubkey::default() {
+        stake_record.owner
+    } else {
+        let token_account = ctx
+            .accounts
+            .position_token_account
+            .as_ref()
+            .ok_or(ErrorCode::InvalidTokenAccount)?;
+        require!(
+            holds_position_nft(token_account, &stake_record.position_mint, &token_account.owner),
+            ErrorCode::InvalidTokenAccount
+        );
+        token_account.owner
     };
-    stake_record.multiplier = multiplier;
+
+    let before = StakeSnapshot::of(stake_record);
+    let commission_bps = position_commission_bps(stake_record, &holder, &ctx.accounts.delegation_record, ctx.program_id)?;
+    accrue_position_rewards(&mut ctx.accounts.reward_pool, stake_record, commission_bps, now);
+    reweight_position(&mut ctx.accounts.reward_pool, stake_record, now);
+    update_stake_totals(&mut ctx.accounts.global_account, before, stake_record);
 
     Ok(())
 }
 
-pub fn unstake_tokens(ctx: Context<UnstakeTokens>) -> Result<()> {
+/// Move `amount` of an expired position into unbonding. It stops counting
+/// toward voting power at once and can be withdrawn after the unbonding period.
+pub fn request_unstake(ctx: Context<RequestUnstake>, _position_id: u64, amount: u64) -> Result<()> {
     let stake_record = &mut ctx.accounts.stake_record;
     let clock = Clock::get()?;
 
     require!(clock.unix_timestamp >= stake_record.lock_end_time, ErrorCode::TokensLocked);
     require!(stake_record.staked_amount > 0, ErrorCode::NoTokensToUnstake);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(amount <= stake_record.staked_amount, ErrorCode::InsufficientStakedBalance);
 
-    let amount = stake_record.staked_amount;
+    let before = StakeSnapshot::of(stake_record);
+    let commission_bps = position_commission_bps(stake_record, &ctx.accounts.user.key(), &ctx.accounts.delegation_record, ctx.program_id)?;
+    accrue_position_rewards(&mut ctx.accounts.reward_pool, stake_record, commission_bps, clock.unix_timestamp);
+    stake_record.staked_amount = stake_record.staked_amount.checked_sub(amount).unwrap();
+    if stake_record.staked_amount == 0 {
+        stake_record.multiplier = MULTIPLIER_BPS;
+        stake_record.lock_end_time = 0;
+    }
+    reweight_position(&mut ctx.accounts.reward_pool, stake_record, clock.unix_timestamp);
+    update_stake_totals(&mut ctx.accounts.global_account, before, stake_record);
+    update_staker_count(&mut ctx.accounts.global_account, &mut ctx.accounts.staker_profile, before, stake_record);
+    start_unbonding(
+        stake_record,
+        amount,
+        load_dao_config(&ctx.accounts.dao_config, ctx.program_id)?.unbonding_period_days,
+        clock.unix_timestamp,
+    );
+
+    emit!(UnstakeRequested {
+        owner: stake_record.owner,
+        position_id: stake_record.position_id,
+        amount,
+        unbonding_end_time: stake_record.unbonding_end_time,
+    });
+
+    Ok(())
+}
 
-    token::transfer(
+/// Release everything that has finished unbonding back to the owner.
+pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>, _position_id: u64) -> Result<()> {
+    let stake_record = &mut ctx.accounts.stake_record;
+    let clock = Clock::get()?;
+
+    require!(stake_record.unbonding_amount > 0, ErrorCode::NoTokensToUnstake);
+    require!(clock.unix_timestamp >= stake_record.unbonding_end_time, ErrorCode::UnbondingInProgress);
+
+    let amount = stake_record.unbonding_amount;
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
+            token_interface::TransferChecked {
                 from: ctx.accounts.vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.user_token_account.to_account_info(),
                 authority: ctx.accounts.vault.to_account_info(),
             },
@@ -156,11 +875,321 @@ pub fn unstake_tokens(ctx: Context<UnstakeTokens>) -> Result<()> {
             ]],
         ),
         amount,
+        ctx.accounts.token_mint.decimals,
     )?;
 
-    stake_record.staked_amount = 0;
-    stake_record.multiplier = 1; 
-    stake_record.lock_end_time = 0;
+    stake_record.unbonding_amount = 0;
+    stake_record.unbonding_end_time = 0;
+
+    emit!(StakeWithdrawn {
+        owner: stake_record.owner,
+        position_id: stake_record.position_id,
+        amount,
+        remaining: stake_record.staked_amount,
+    });
     
     Ok(())
 }
+
+////////////////////////////////////////////////////////////////
+//                      STAKING HELPERS
+////////////////////////////////////////////////////////////////
+
+pub const MULTIPLIER_BPS: u64 = 10_000;
+
+pub const MIN_LOCK_DAYS: i64 = 30;
+pub const MAX_LOCK_DAYS: i64 = 360;
+pub const MIN_LOCK_MULTIPLIER_BPS: u64 = 20_000;   // 2x at MIN_LOCK_DAYS
+pub const MAX_LOCK_MULTIPLIER_BPS: u64 = 50_000;   // 5x at MAX_LOCK_DAYS
+
+pub fn lock_seconds(lock_days: i64) -> i64 {
+    days_to_seconds(lock_days)
+}
+
+/// When a lock of `lock_days` taken at `now` ends. Out-of-range input fails
+/// with `InvalidLockDuration` instead of overflowing.
+pub fn lock_end_time(now: i64, lock_days: i64) -> Result<i64> {
+    lock_days
+        .checked_mul(SECONDS_PER_DAY)
+        .and_then(|seconds| now.checked_add(seconds))
+        .ok_or_else(|| error!(ErrorCode::InvalidLockDuration))
+}
+
+/// Lock multiplier (basis points), interpolated linearly between
+/// `MIN_LOCK_DAYS` (2x) and `MAX_LOCK_DAYS` (5x).
+pub fn multiplier_for_lock(lock_days: i64) -> Result<u64> {
+    require!(
+        (MIN_LOCK_DAYS..=MAX_LOCK_DAYS).contains(&lock_days),
+        ErrorCode::InvalidLockDuration
+    );
+
+    let span = (MAX_LOCK_MULTIPLIER_BPS - MIN_LOCK_MULTIPLIER_BPS) as i64;
+    let bonus = span * (lock_days - MIN_LOCK_DAYS) / (MAX_LOCK_DAYS - MIN_LOCK_DAYS);
+    Ok(MIN_LOCK_MULTIPLIER_BPS + bonus as u64)
+}
+
+/// Vote-escrow multiplier (basis points) of a stake at time `at`.
+/// The bonus above 1x decays linearly from the full lock multiplier at
+/// lock start to nothing at `lock_end_time`. Re-locking restores it.
+pub fn voting_multiplier_bps(stake_record: &VoterStakeRecord, at: i64) -> u64 {
+    let lock_duration = lock_seconds(stake_record.original_lock_days);
+    if lock_duration <= 0 || stake_record.multiplier <= MULTIPLIER_BPS {
+        return MULTIPLIER_BPS;
+    }
+
+    let remaining = stake_record.lock_end_time.saturating_sub(at).clamp(0, lock_duration);
+    let max_bonus = (stake_record.multiplier - MULTIPLIER_BPS) as u128;
+    let bonus = max_bonus * remaining as u128 / lock_duration as u128;
+
+    MULTIPLIER_BPS + bonus as u64
+}
+
+/// `penalty_bps` applies to a lock that has just started and shrinks
+/// linearly to zero at `lock_end_time`.
+pub fn early_unstake_penalty(stake_record: &VoterStakeRecord, penalty_bps: u16, at: i64) -> u64 {
+    let lock_duration = lock_seconds(stake_record.original_lock_days);
+    if lock_duration <= 0 {
+        return 0;
+    }
+
+    let remaining = stake_record.lock_end_time.saturating_sub(at).clamp(0, lock_duration);
+    let penalty = stake_record.staked_amount as u128 * penalty_bps.min(MAX_BPS) as u128 * remaining as u128
+        / (MAX_BPS as u128 * lock_duration as u128);
+    penalty as u64
+}
+
+/// Adds `amount` to the position's unbonding balance. A new request restarts
+/// the cooldown for the whole unbonding balance.
+fn start_unbonding(stake_record: &mut VoterStakeRecord, amount: u64, unbonding_period_days: i64, now: i64) {
+    stake_record.unbonding_amount = stake_record.unbonding_amount.checked_add(amount).unwrap();
+    stake_record.unbonding_end_time = now + days_to_seconds(unbonding_period_days);
+}
+
+/// Tokens that actually arrived in `token_account` since `balance_before`.
+/// Differs from the amount sent when the mint charges a transfer fee.
+pub fn received_amount(token_account: &mut InterfaceAccount<TokenAccount>, balance_before: u64) -> Result<u64> {
+    token_account.reload()?;
+    Ok(token_account.amount.checked_sub(balance_before).unwrap())
+}
+
+/// Stake weighted by the lock multiplier as set at lock start while the
+/// lock runs, and at 1x once it has ended. Used for reward shares and the
+/// global weighted total, which hold a position's weight as of its last
+/// change (`reward_weight`) until it is touched again or `expire_lock` runs.
+pub fn weighted_stake(stake_record: &VoterStakeRecord, at: i64) -> u64 {
+    if at >= stake_record.lock_end_time {
+        return stake_record.staked_amount;
+    }
+    (stake_record.staked_amount as u128 * stake_record.multiplier as u128 / MULTIPLIER_BPS as u128) as u64
+}
+
+/// A position's contribution to the global totals, taken before it changes.
+#[derive(Clone, Copy)]
+pub struct StakeSnapshot {
+    pub staked_amount: u64,
+    pub weighted_stake: u64,
+}
+
+impl StakeSnapshot {
+    pub fn of(stake_record: &VoterStakeRecord) -> Self {
+        StakeSnapshot {
+            staked_amount: stake_record.staked_amount,
+            weighted_stake: stake_record.reward_weight,
+        }
+    }
+}
+
+/// Move the global staked and weighted totals from `before` to the
+/// position's current state. Must follow `reweight_position`. Unbonding
+/// tokens are not counted as staked.
+pub fn update_stake_totals(global_account: &mut GlobalAccount, before: StakeSnapshot, stake_record: &VoterStakeRecord) {
+    global_account.total_staked = global_account
+        .total_staked
+        .checked_sub(before.staked_amount)
+        .unwrap()
+        .checked_add(stake_record.staked_amount)
+        .unwrap();
+    global_account.total_weighted_stake = global_account
+        .total_weighted_stake
+        .checked_sub(before.weighted_stake)
+        .unwrap()
+        .checked_add(stake_record.reward_weight)
+        .unwrap();
+}
+
+/// A staker is counted while at least one of their positions holds tokens.
+pub fn update_staker_count(
+    global_account: &mut GlobalAccount,
+    staker_profile: &mut StakerProfile,
+    before: StakeSnapshot,
+    stake_record: &VoterStakeRecord,
+) {
+    match (before.staked_amount > 0, stake_record.staked_amount > 0) {
+        (false, true) => {
+            staker_profile.active_positions = staker_profile.active_positions.checked_add(1).unwrap();
+            if staker_profile.active_positions == 1 {
+                global_account.staker_count = global_account.staker_count.checked_add(1).unwrap();
+            }
+        }
+        (true, false) => {
+            staker_profile.active_positions = staker_profile.active_positions.checked_sub(1).unwrap();
+            if staker_profile.active_positions == 0 {
+                global_account.staker_count = global_account.staker_count.checked_sub(1).unwrap();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A tokenized position is controlled by whoever holds its NFT, otherwise
+/// by the wallet that opened it.
+pub fn is_position_authority(
+    stake_record: &VoterStakeRecord,
+    authority: &Pubkey,
+    position_token_account: &Option<InterfaceAccount<TokenAccount>>,
+) -> bool {
+    if stake_record.position_mint == Pubkey::default() {
+        return stake_record.owner == *authority;
+    }
+
+    match position_token_account {
+        Some(token_account) => holds_position_nft(token_account, &stake_record.position_mint, authority),
+        None => false,
+    }
+}
+
+fn holds_position_nft(token_account: &TokenAccount, position_mint: &Pubkey, holder: &Pubkey) -> bool {
+    token_account.mint == *position_mint && token_account.owner == *holder && token_account.amount == 1
+}
+
+/// Whether `info` is `holder`'s token account holding the NFT minted as `position_mint`.
+pub fn is_position_nft_account(info: &AccountInfo, position_mint: &Pubkey, holder: &Pubkey) -> Result<bool> {
+    if *info.owner != anchor_spl::token::ID && *info.owner != anchor_spl::token_2022::ID {
+        return Ok(false);
+    }
+    let token_account = TokenAccount::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    Ok(holds_position_nft(&token_account, position_mint, holder))
+}
+
+/// Reads the stake positions passed through remaining_accounts for a vote.
+/// Every position must be a program-owned `VoterStakeRecord` belonging to
+/// `owner`, and none may appear twice. A tokenized position belongs to the
+/// holder of its NFT and must be followed by their token account for it.
+pub fn load_stake_positions(
+    accounts: &[AccountInfo],
+    owner: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Vec<VoterStakeRecord>> {
+    let mut positions: Vec<VoterStakeRecord> = Vec::with_capacity(accounts.len());
+    let mut position_keys: Vec<Pubkey> = Vec::with_capacity(accounts.len());
+    let mut iter = accounts.iter();
+    while let Some(info) = iter.next() {
+        require_keys_eq!(*info.owner, *program_id, ErrorCode::InvalidStakePosition);
+        require!(!position_keys.contains(info.key), ErrorCode::InvalidStakePosition);
+
+        let position = VoterStakeRecord::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        if position.position_mint == Pubkey::default() {
+            require_keys_eq!(position.owner, *owner, ErrorCode::InvalidStakePosition);
+        } else {
+            let nft_info = iter.next().ok_or(ErrorCode::InvalidStakePosition)?;
+            require!(
+                *nft_info.owner == anchor_spl::token::ID || *nft_info.owner == anchor_spl::token_2022::ID,
+                ErrorCode::InvalidStakePosition
+            );
+            let token_account = TokenAccount::try_deserialize(&mut &nft_info.try_borrow_data()?[..])?;
+            require!(
+                holds_position_nft(&token_account, &position.position_mint, owner),
+                ErrorCode::InvalidStakePosition
+            );
+        }
+
+        position_keys.push(*info.key);
+        positions.push(position);
+    }
+    Ok(positions)
+}
+
+/// Reserves the positions passed to a vote for `voter` until `until`, the
+/// deadline of the proposal voted on. Call after `load_stake_positions` has
+/// validated the accounts. A position still reserved by another wallet, for
+/// instance before its NFT was sold, is rejected, so the same stake is never
+/// counted twice while those votes are live.
+pub fn reserve_stake_positions(
+    accounts: &[AccountInfo],
+    voter: &Pubkey,
+    until: i64,
+    now: i64,
+    program_id: &Pubkey,
+) -> Result<()> {
+    for info in accounts.iter().filter(|info| info.owner == program_id) {
+        require!(info.is_writable, ErrorCode::InvalidStakePosition);
+
+        let mut position = VoterStakeRecord::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        reserve_stake_position(&mut position, voter, until, now)?;
+        position.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+    }
+    Ok(())
+}
+
+/// Reserves one position for `voter` until `until`. See `reserve_stake_positions`.
+pub fn reserve_stake_position(position: &mut VoterStakeRecord, voter: &Pubkey, until: i64, now: i64) -> Result<()> {
+    if position.vote_holder == *voter {
+        position.vote_reserved_until = position.vote_reserved_until.max(until);
+    } else {
+        require!(position.vote_reserved_until < now, ErrorCode::PositionVoteReserved);
+        position.vote_holder = *voter;
+        position.vote_reserved_until = until;
+    }
+    Ok(())
+}
+
+/// Reads every one of the `position_count` positions `owner` opened, in
+/// position id order, followed by any tokenized positions they hold from
+/// others. Unlike `load_stake_positions`, none of the owner's own positions
+/// can be left out. One of their tokenized positions only counts when
+/// followed by their token account for its NFT; without it, someone else
+/// holds it.
+pub fn load_all_stake_positions(
+    accounts: &[AccountInfo],
+    owner: &Pubkey,
+    position_count: u64,
+    program_id: &Pubkey,
+) -> Result<Vec<VoterStakeRecord>> {
+    let owner = *owner;
+    let mut positions: Vec<VoterStakeRecord> = Vec::with_capacity(accounts.len());
+    let mut index = 0;
+    for position_id in 0..position_count {
+        let info = accounts.get(index).ok_or(ErrorCode::MissingStakePosition)?;
+        index += 1;
+        let (expected, _) = Pubkey::find_program_address(
+            &[STAKE_RECORD_SEED, owner.as_ref(), position_id.to_le_bytes().as_ref()],
+            program_id,
+        );
+        require_keys_eq!(*info.key, expected, ErrorCode::MissingStakePosition);
+        require_keys_eq!(*info.owner, *program_id, ErrorCode::InvalidStakePosition);
+
+        let position = VoterStakeRecord::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        if position.position_mint == Pubkey::default() {
+            positions.push(position);
+            continue;
+        }
+        let nft_info = accounts
+            .get(index)
+            .filter(|next| *next.owner == anchor_spl::token::ID || *next.owner == anchor_spl::token_2022::ID);
+        if let Some(nft_info) = nft_info {
+            index += 1;
+            let token_account = TokenAccount::try_deserialize(&mut &nft_info.try_borrow_data()?[..])?;
+            require!(
+                holds_position_nft(&token_account, &position.position_mint, &owner),
+                ErrorCode::InvalidStakePosition
+            );
+            positions.push(position);
+        }
+    }
+
+    // Own positions were all read above and may not be passed again
+    let held = load_stake_positions(&accounts[index..], &owner, program_id)?;
+    require!(held.iter().all(|position| position.owner != owner), ErrorCode::InvalidStakePosition);
+    positions.extend(held);
+    Ok(positions)
+}
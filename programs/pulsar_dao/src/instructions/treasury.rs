@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, Mint, TokenAccount};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::error::ErrorCode;
 use crate::instructions::admin::GLOBAL_ACCOUNT_SEED;
@@ -31,20 +31,20 @@ pub struct ExecuteProposal<'info> {
         seeds = [PROPOSAL_ESCROW_SEED, proposal_number.to_le_bytes().as_ref()],
         bump,
     )]
-    pub proposal_escrow: Account<'info, TokenAccount>,
+    pub proposal_escrow: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: Destination token account - will be validated in instruction
     #[account(mut)]
-    pub destination_token_account: Account<'info, TokenAccount>,
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         constraint = token_mint.key() == global_account.token_mint
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(mut)]
     pub executor: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -70,23 +70,23 @@ pub struct ReclaimProposalFunds<'info> {
         seeds = [PROPOSAL_ESCROW_SEED, proposal_number.to_le_bytes().as_ref()],
         bump,
     )]
-    pub proposal_escrow: Account<'info, TokenAccount>,
+    pub proposal_escrow: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         constraint = author_token_account.mint == global_account.token_mint,
         constraint = author_token_account.owner == author.key()
     )]
-    pub author_token_account: Account<'info, TokenAccount>,
+    pub author_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         constraint = token_mint.key() == global_account.token_mint
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(mut)]
     pub author: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 ////////////////////////////////////////////////////////////////
@@ -103,6 +103,7 @@ pub fn execute_proposal(
 
     // Validate proposal state
     require!(!proposal_account.executed, ErrorCode::AlreadyExecuted);
+    require!(!proposal_account.vetoed, ErrorCode::ProposalVetoed);
     require!(clock.unix_timestamp > proposal_account.deadline, ErrorCode::ProposalNotEnded);
     
     // Check timelock
@@ -127,17 +128,19 @@ pub fn execute_proposal(
     ];
     let signer_seeds = &[&seeds[..]];
 
-    anchor_spl::token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Transfer {
+            token_interface::TransferChecked {
                 from: ctx.accounts.proposal_escrow.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.destination_token_account.to_account_info(),
                 authority: ctx.accounts.proposal_escrow.to_account_info(),
             },
             signer_seeds,
         ),
         proposal_account.transfer_amount,
+        ctx.accounts.token_mint.decimals,
     )?;
 
     proposal_account.executed = true;
@@ -166,8 +169,11 @@ pub fn reclaim_proposal_funds(
     require!(!proposal_account.executed, ErrorCode::AlreadyExecuted);
     require!(clock.unix_timestamp > proposal_account.deadline, ErrorCode::ProposalNotEnded);
     
-    // Check vote result - can only reclaim if NO >= YES
-    require!(proposal_account.no >= proposal_account.yes, ErrorCode::ProposalPassed);
+    // Check vote result - can only reclaim if NO >= YES, or the council vetoed it
+    require!(
+        proposal_account.no >= proposal_account.yes || proposal_account.vetoed,
+        ErrorCode::ProposalPassed
+    );
 
     // Transfer from escrow back to author
     let proposal_number_bytes = proposal_number.to_le_bytes();
@@ -178,17 +184,19 @@ pub fn reclaim_proposal_funds(
     ];
     let signer_seeds = &[&seeds[..]];
 
-    anchor_spl::token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Transfer {
+            token_interface::TransferChecked {
                 from: ctx.accounts.proposal_escrow.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.author_token_account.to_account_info(),
                 authority: ctx.accounts.proposal_escrow.to_account_info(),
             },
             signer_seeds,
         ),
         proposal_account.transfer_amount,
+        ctx.accounts.token_mint.decimals,
     )?;
 
     proposal_account.executed = true;
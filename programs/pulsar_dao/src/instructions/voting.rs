@@ -1,16 +1,35 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::TokenAccount;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_spl::token_interface::TokenAccount;
 use crate::state::*;
 use crate::error::ErrorCode;
 use crate::instructions::admin::GLOBAL_ACCOUNT_SEED;
-use crate::instructions::delegation::{DELEGATE_PROFILE_SEED, DELEGATION_RECORD_SEED};
+use crate::instructions::admin::MAX_BPS;
+use crate::instructions::delegation::{
+    DELEGATE_PARTICIPATION_SEED, DELEGATE_PARTICIPATION_SPACE, DELEGATE_PROFILE_SEED, DELEGATION_RECORD_SEED,
+    MAX_DELEGATE_SHARES, delegate_share_bps, record_proxy_participation, require_delegation_covers,
+};
+use crate::instructions::staking::{load_stake_positions, reserve_stake_positions, voting_multiplier_bps, MULTIPLIER_BPS};
 
 pub const USER_STATS_SEED: &[u8] = b"user_stats_v2";
+pub const VOTER_RECORD_SEED: &[u8] = b"voter";
+pub const VOTE_NONCE_SEED: &[u8] = b"vote_nonce";
+
+// Space: 8 (discriminator) + 32 (proposal) + 32 (voter) + 1 (vote) + 1 (voted) + 8 (voting_power) + 8 (staked_amount) + 1 (voted_by_proxy)
+//        + 4 (proxy_votes len) + MAX_DELEGATE_SHARES * (32 (delegate) + 1 (vote) + 8 (voting_power)) + 1 (overridden)
+pub const VOTER_RECORD_SPACE: usize = 8 + 32 + 32 + 1 + 1 + 8 + 8 + 1 + 4 + MAX_DELEGATE_SHARES * (32 + 1 + 8) + 1;
+
+pub const MAX_BATCH_VOTES: usize = 10;
 
 ////////////////////////////////////////////////////////////////
 //                      VOTING CONTEXTS
 ////////////////////////////////////////////////////////////////
 
+/// The voter's stake positions are passed, writable, through `remaining_accounts`.
 #[derive(Accounts)]
 pub struct VoteProposal<'info> {
     #[account(
@@ -25,32 +44,17 @@ pub struct VoteProposal<'info> {
     #[account(
         init_if_needed,
         payer = user,
-        // Space: 8 (discriminator) + 32 (proposal) + 32 (voter) + 1 (vote) + 1 (voted) + 8 (voting_power) + 8 (staked_amount) + 1 (voted_by_proxy)
-        space = 8 + 32 + 32 + 1 + 1 + 8 + 8 + 1,
-        seeds = [b"voter", proposal_account.key().as_ref(), user.key().as_ref()],
+        space = VOTER_RECORD_SPACE,
+        seeds = [VOTER_RECORD_SEED, proposal_account.key().as_ref(), user.key().as_ref()],
         bump
     )]
     pub voter_record: Account<'info, VoterRecord>,
 
-    #[account(
-        mut,
-        seeds = [b"stake_record", user.key().as_ref()],
-        bump,
-    )]
-    pub stake_record: Option<Account<'info, VoterStakeRecord>>,
-
     #[account(
         constraint = user_token_account.mint == global_account.token_mint,
         constraint = user_token_account.owner == user.key()
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    /// CHECK: Checked in instruction to ensure user is NOT delegating
-    #[account(
-        seeds = [DELEGATION_RECORD_SEED, user.key().as_ref()],
-        bump
-    )]
-    pub delegation_record: UncheckedAccount<'info>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
@@ -78,7 +82,7 @@ pub struct WithdrawVote<'info> {
     pub proposal_account: Account<'info, ProposalAccount>,
     #[account(
         mut,
-        seeds = [b"voter", proposal_account.key().as_ref(), user.key().as_ref()],
+        seeds = [VOTER_RECORD_SEED, proposal_account.key().as_ref(), user.key().as_ref()],
         bump,
         constraint = voter_record.voter == user.key()
     )]
@@ -87,6 +91,7 @@ pub struct WithdrawVote<'info> {
     pub user: Signer<'info>,
 }
 
+/// The delegator's stake positions are passed, writable, through `remaining_accounts`.
 #[derive(Accounts)]
 pub struct VoteAsProxy<'info> {
     #[account(
@@ -99,11 +104,21 @@ pub struct VoteAsProxy<'info> {
     pub proposal_account: Account<'info, ProposalAccount>,
 
     #[account(
+        mut,
         seeds = [DELEGATE_PROFILE_SEED, proxy_authority.key().as_ref()],
         bump,
     )]
     pub delegate_profile: Account<'info, DelegateProfile>,
 
+    #[account(
+        init_if_needed,
+        payer = proxy_authority,
+        space = DELEGATE_PARTICIPATION_SPACE,
+        seeds = [DELEGATE_PARTICIPATION_SEED, proposal_account.key().as_ref(), proxy_authority.key().as_ref()],
+        bump
+    )]
+    pub participation: Account<'info, DelegateParticipation>,
+
     #[account(
         seeds = [DELEGATION_RECORD_SEED, delegator_user.key().as_ref()],
         bump,
@@ -113,9 +128,8 @@ pub struct VoteAsProxy<'info> {
     #[account(
         init_if_needed,
         payer = proxy_authority,
-        // Space: 8 (discriminator) + 32 (proposal) + 32 (voter) + 1 (vote) + 1 (voted) + 8 (voting_power) + 8 (staked_amount) + 1 (voted_by_proxy)
-        space = 8 + 32 + 32 + 1 + 1 + 8 + 8 + 1,
-        seeds = [b"voter", proposal_account.key().as_ref(), delegator_user.key().as_ref()],
+        space = VOTER_RECORD_SPACE,
+        seeds = [VOTER_RECORD_SEED, proposal_account.key().as_ref(), delegator_user.key().as_ref()],
         bump
     )]
     pub voter_record: Account<'info, VoterRecord>,
@@ -125,11 +139,7 @@ pub struct VoteAsProxy<'info> {
         constraint = delegator_token_account.mint == global_account.token_mint,
         constraint = delegator_token_account.owner == delegator_user.key()
     )]
-    pub delegator_token_account: Account<'info, TokenAccount>,
-
-    /// Optional stake record for delegator (may not exist if never staked)
-    /// CHECK: We verify the seeds manually if Some
-    pub delegator_stake_record: Option<Account<'info, VoterStakeRecord>>,
+    pub delegator_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: The user who is being voted FOR. They don't sign.
     pub delegator_user: UncheckedAccount<'info>,
@@ -175,7 +185,7 @@ pub struct WithdrawAsProxy<'info> {
 
     #[account(
         mut,
-        seeds = [b"voter", proposal_account.key().as_ref(), delegator_user.key().as_ref()],
+        seeds = [VOTER_RECORD_SEED, proposal_account.key().as_ref(), delegator_user.key().as_ref()],
         bump
     )]
     pub voter_record: Account<'info, VoterRecord>,
@@ -187,6 +197,187 @@ pub struct WithdrawAsProxy<'info> {
     pub proxy_authority: Signer<'info>,
 }
 
+/// Anyone may strike a suspended or removed delegate's proxy vote from an
+/// open proposal.
+#[derive(Accounts)]
+pub struct ClearProxyVotes<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump,
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(mut)]
+    pub proposal_account: Account<'info, ProposalAccount>,
+
+    /// CHECK: The delegate's profile, inactive or already closed
+    #[account(
+        seeds = [DELEGATE_PROFILE_SEED, delegate.key().as_ref()],
+        bump,
+    )]
+    pub delegate_profile: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VOTER_RECORD_SEED, proposal_account.key().as_ref(), delegator_user.key().as_ref()],
+        bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+
+    /// CHECK: The suspended or removed delegate
+    pub delegate: UncheckedAccount<'info>,
+
+    /// CHECK: The delegator whose voter record is cleaned up
+    pub delegator_user: UncheckedAccount<'info>,
+}
+
+/// Accounts for `vote_as_proxy_batch`. Each delegator contributes a
+/// `(delegation_record, token_account, stake positions.., voter_record)`
+/// tuple through `remaining_accounts`. The number of stake position
+/// accounts in each tuple is given by `position_account_counts`.
+#[derive(Accounts)]
+pub struct VoteAsProxyBatch<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(mut)]
+    pub proposal_account: Account<'info, ProposalAccount>,
+
+    #[account(
+        mut,
+        seeds = [DELEGATE_PROFILE_SEED, proxy_authority.key().as_ref()],
+        bump,
+    )]
+    pub delegate_profile: Account<'info, DelegateProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = proxy_authority,
+        space = DELEGATE_PARTICIPATION_SPACE,
+        seeds = [DELEGATE_PARTICIPATION_SEED, proposal_account.key().as_ref(), proxy_authority.key().as_ref()],
+        bump
+    )]
+    pub participation: Account<'info, DelegateParticipation>,
+
+    #[account(
+        init_if_needed,
+        payer = proxy_authority,
+        // Space: 8 (discriminator) + 32 (user) + 8 (proposal_count) + 8 (last_vote_time) + 8 (score) + 1 (badge_claimed)
+        space = 8 + 32 + 8 + 8 + 8 + 1,
+        seeds = [USER_STATS_SEED, proxy_authority.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(mut)]
+    pub proxy_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for `vote_batch`. Each entry contributes a
+/// `(proposal_account, voter_record)` pair through `remaining_accounts`,
+/// in the same order as the `entries` argument, followed by the voter's
+/// stake positions.
+#[derive(Accounts)]
+pub struct VoteBatch<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(
+        constraint = user_token_account.mint == global_account.token_mint,
+        constraint = user_token_account.owner == user.key()
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        // Space: 8 (discriminator) + 32 (user) + 8 (proposal_count) + 8 (last_vote_time) + 8 (score) + 1 (badge_claimed)
+        space = 8 + 32 + 8 + 8 + 8 + 1,
+        seeds = [USER_STATS_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Relayed vote: `voter` signs the message off-chain, `relayer` submits it
+/// right after an ed25519 precompile instruction and pays for any accounts.
+/// The voter's stake positions are passed, writable, through `remaining_accounts`.
+#[derive(Accounts)]
+pub struct VoteWithSignature<'info> {
+    #[account(
+        seeds = [GLOBAL_ACCOUNT_SEED],
+        bump
+    )]
+    pub global_account: Account<'info, GlobalAccount>,
+
+    #[account(mut)]
+    pub proposal_account: Account<'info, ProposalAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = VOTER_RECORD_SPACE,
+        seeds = [VOTER_RECORD_SEED, proposal_account.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+
+    #[account(
+        constraint = voter_token_account.mint == global_account.token_mint,
+        constraint = voter_token_account.owner == voter.key()
+    )]
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        // Space: 8 (discriminator) + 32 (voter) + 8 (next_nonce)
+        space = 8 + 32 + 8,
+        seeds = [VOTE_NONCE_SEED, voter.key().as_ref()],
+        bump
+    )]
+    pub vote_nonce: Account<'info, VoteNonce>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        // Space: 8 (discriminator) + 32 (user) + 8 (proposal_count) + 8 (last_vote_time) + 8 (score) + 1 (badge_claimed)
+        space = 8 + 32 + 8 + 8 + 8 + 1,
+        seeds = [USER_STATS_SEED, voter.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: The vote author. Authenticated by the ed25519 signature check.
+    pub voter: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, used to read the ed25519 instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchVoteEntry {
+    pub proposal: Pubkey,
+    pub vote_yes: bool,
+}
+
 ////////////////////////////////////////////////////////////////
 //                      VOTING HANDLERS
 ////////////////////////////////////////////////////////////////
@@ -197,90 +388,182 @@ pub fn vote(ctx: Context<VoteProposal>, vote_yes: bool) -> Result<()> {
 
     let proposal_account = &mut ctx.accounts.proposal_account;
     let voter_record = &mut ctx.accounts.voter_record;
-    let user_token_account = &ctx.accounts.user_token_account;
     let clock = Clock::get()?;
 
-    require!(proposal_account.is_active, ErrorCode::ProposalNotActive);
-    require!(clock.unix_timestamp <= proposal_account.deadline, ErrorCode::ProposalExpired);
-    
-    // Security: Prevent double voting (delegators cannot vote directly)
-    require!(ctx.accounts.delegation_record.data_is_empty(), ErrorCode::DelegatorsCannotVote);
-
-    // Calculate voting power
-    let liquid_amount = user_token_account.amount;
-    let liquid_power = (liquid_amount as f64).sqrt() as u64;
+    require_proposal_open(proposal_account, clock.unix_timestamp)?;
+
+    let positions = load_stake_positions(ctx.remaining_accounts, &ctx.accounts.user.key(), ctx.program_id)?;
+    let power = calculate_voting_power(
+        ctx.accounts.user_token_account.amount,
+        &positions,
+        clock.unix_timestamp,
+    );
+    require!(power.total > 0, ErrorCode::NoVotingPower);
+    reserve_stake_positions(
+        ctx.remaining_accounts,
+        &ctx.accounts.user.key(),
+        proposal_account.deadline,
+        clock.unix_timestamp,
+        ctx.program_id,
+    )?;
+
+    apply_direct_vote(
+        proposal_account,
+        voter_record,
+        ctx.accounts.user.key(),
+        vote_yes,
+        &power,
+    )?;
+
+    award_vote_points(&mut ctx.accounts.user_stats, ctx.accounts.user.key(), clock.unix_timestamp, 1);
 
-    let (staked_amount, multiplier) = if let Some(stake_record) = &ctx.accounts.stake_record {
-        (stake_record.staked_amount, stake_record.multiplier)
-    } else {
-        (0, 1)
-    };
+    emit!(VoteCast {
+        voter: ctx.accounts.user.key(),
+        proposal: proposal_account.key(),
+        amount: power.staked_amount,
+        lock_duration: power.lock_duration,
+        voting_power: power.total,
+        multiplier: power.multiplier,
+    });
 
-    let staked_sqrt = (staked_amount as f64).sqrt() as u64;
-    let staked_power = staked_sqrt.checked_mul(multiplier).unwrap_or(0);
-    let total_voting_power = liquid_power.checked_add(staked_power).unwrap();
+    Ok(())
+}
 
-    require!(total_voting_power > 0, ErrorCode::NoVotingPower);
+/// Cast direct votes on several proposals in one transaction.
+/// Voting power is computed once and every entry goes through the same
+/// checks as `vote`. With `best_effort` set, a failing entry is reported
+/// through `BatchVoteEntryFailed` and the rest of the batch still lands.
+pub fn vote_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VoteBatch<'info>>,
+    entries: Vec<BatchVoteEntry>,
+    best_effort: bool,
+) -> Result<()> {
+    let global_account = &ctx.accounts.global_account;
+    require!(global_account.system_enabled, ErrorCode::CircuitBreakerTripped);
 
-    if voter_record.voted {
-        // Proxy votes are locked
-        require!(!voter_record.voted_by_proxy, ErrorCode::ProxyVoteLocked);
-        
-        // Prevent voting for same option (must switch)
-        require!(voter_record.vote != vote_yes, ErrorCode::AlreadyVoted);
+    require!(
+        !entries.is_empty() && entries.len() <= MAX_BATCH_VOTES,
+        ErrorCode::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() >= entries.len() * 2,
+        ErrorCode::InvalidBatchAccounts
+    );
+    let (vote_accounts, position_accounts) = ctx.remaining_accounts.split_at(entries.len() * 2);
 
-        // Remove old vote weight
-        if voter_record.vote {
-            proposal_account.yes = proposal_account.yes.checked_sub(voter_record.voting_power).unwrap();
-        } else {
-            proposal_account.no = proposal_account.no.checked_sub(voter_record.voting_power).unwrap();
+    let clock = Clock::get()?;
+    let positions = load_stake_positions(position_accounts, &ctx.accounts.user.key(), ctx.program_id)?;
+    let power = calculate_voting_power(
+        ctx.accounts.user_token_account.amount,
+        &positions,
+        clock.unix_timestamp,
+    );
+    require!(power.total > 0, ErrorCode::NoVotingPower);
+
+    let user = ctx.accounts.user.key();
+    let mut succeeded: u16 = 0;
+    let mut failed: u16 = 0;
+    let mut reserve_until: i64 = 0;
+
+    for (index, entry) in entries.iter().enumerate() {
+        let proposal_info = &vote_accounts[index * 2];
+        let voter_record_info = &vote_accounts[index * 2 + 1];
+
+        let result = cast_batch_vote(
+            proposal_info,
+            voter_record_info,
+            entry,
+            &power,
+            clock.unix_timestamp,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.program_id,
+        );
+
+        match result {
+            Ok(deadline) => {
+                succeeded += 1;
+                reserve_until = reserve_until.max(deadline);
+            }
+            Err(err) if best_effort => {
+                failed += 1;
+                emit!(BatchVoteEntryFailed {
+                    voter: user,
+                    proposal: entry.proposal,
+                    index: index as u16,
+                    error_code: error_code_of(err),
+                });
+            }
+            Err(err) => return Err(err),
         }
+    }
 
-        // Add new vote weight
-        if vote_yes {
-            proposal_account.yes = proposal_account.yes.checked_add(total_voting_power).unwrap();
-        } else {
-            proposal_account.no = proposal_account.no.checked_add(total_voting_power).unwrap();
-        }
-        
-        voter_record.vote = vote_yes;
-        voter_record.voting_power = total_voting_power;
-        voter_record.staked_amount = staked_amount;
-        voter_record.voted_by_proxy = false;
+    if succeeded > 0 {
+        reserve_stake_positions(position_accounts, &user, reserve_until, clock.unix_timestamp, ctx.program_id)?;
+        award_vote_points(&mut ctx.accounts.user_stats, user, clock.unix_timestamp, succeeded as u64);
+    }
 
-    } else {
-        // First time vote
-        if vote_yes {
-            proposal_account.yes = proposal_account.yes.checked_add(total_voting_power).unwrap();
-        } else {
-            proposal_account.no = proposal_account.no.checked_add(total_voting_power).unwrap();
-        }
+    emit!(BatchVoteProcessed {
+        voter: user,
+        succeeded,
+        failed,
+        voting_power: power.total,
+    });
 
-        voter_record.proposal = proposal_account.key();
-        voter_record.voter = ctx.accounts.user.key();
-        voter_record.vote = vote_yes;
-        voter_record.voted = true;
-        voter_record.voting_power = total_voting_power;
-        voter_record.staked_amount = staked_amount;
-    }
+    Ok(())
+}
 
-    // Update User Stats
-    let user_stats = &mut ctx.accounts.user_stats;
-    if user_stats.proposal_count == 0 {
-        user_stats.user = ctx.accounts.user.key();
-        user_stats.score = 0;
-    }
-    user_stats.proposal_count = user_stats.proposal_count.checked_add(1).unwrap();
-    user_stats.last_vote_time = clock.unix_timestamp;
-    user_stats.score = user_stats.score.checked_add(10).unwrap(); // 10 points per vote
+/// Tally a vote signed off-chain by `voter` and submitted by any relayer.
+/// The preceding instruction must be an ed25519 precompile check over
+/// `signed_vote_message(..)`. Nonces are strictly sequential per voter.
+pub fn vote_with_signature(
+    ctx: Context<VoteWithSignature>,
+    vote_yes: bool,
+    nonce: u64,
+    expiry: i64,
+) -> Result<()> {
+    let global_account = &ctx.accounts.global_account;
+    require!(global_account.system_enabled, ErrorCode::CircuitBreakerTripped);
+
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp <= expiry, ErrorCode::SignatureExpired);
+
+    let voter = ctx.accounts.voter.key();
+    let proposal_key = ctx.accounts.proposal_account.key();
+
+    // Security: Verify the voter signed exactly this vote
+    let message = signed_vote_message(ctx.program_id, &proposal_key, vote_yes, nonce, expiry);
+    verify_ed25519_signature(&ctx.accounts.instructions, &voter, &message)?;
+
+    // Security: Replay protection
+    let vote_nonce = &mut ctx.accounts.vote_nonce;
+    require!(nonce == vote_nonce.next_nonce, ErrorCode::InvalidNonce);
+    vote_nonce.voter = voter;
+    vote_nonce.next_nonce = nonce.checked_add(1).unwrap();
+
+    let proposal_account = &mut ctx.accounts.proposal_account;
+    require_proposal_open(proposal_account, clock.unix_timestamp)?;
+
+    let positions = load_stake_positions(ctx.remaining_accounts, &voter, ctx.program_id)?;
+    let power = calculate_voting_power(
+        ctx.accounts.voter_token_account.amount,
+        &positions,
+        clock.unix_timestamp,
+    );
+    require!(power.total > 0, ErrorCode::NoVotingPower);
+    reserve_stake_positions(ctx.remaining_accounts, &voter, proposal_account.deadline, clock.unix_timestamp, ctx.program_id)?;
+
+    apply_direct_vote(proposal_account, &mut ctx.accounts.voter_record, voter, vote_yes, &power)?;
+
+    award_vote_points(&mut ctx.accounts.user_stats, voter, clock.unix_timestamp, 1);
 
     emit!(VoteCast {
-        voter: ctx.accounts.user.key(),
-        proposal: proposal_account.key(),
-        amount: staked_amount,
-        lock_duration: if let Some(r) = &ctx.accounts.stake_record { r.original_lock_days } else { 0 },
-        voting_power: total_voting_power,
-        multiplier: multiplier,
+        voter,
+        proposal: proposal_key,
+        amount: power.staked_amount,
+        lock_duration: power.lock_duration,
+        voting_power: power.total,
+        multiplier: power.multiplier,
     });
 
     Ok(())
@@ -297,9 +580,9 @@ pub fn withdraw_vote(ctx: Context<WithdrawVote>) -> Result<()> {
     require!(proposal_account.is_active, ErrorCode::ProposalNotActive);
     require!(clock.unix_timestamp <= proposal_account.deadline, ErrorCode::ProposalExpired);
 
-    if voter_record.voted {
-        require!(!voter_record.voted_by_proxy, ErrorCode::ProxyVoteLocked);
+    require!(!voter_record.voted_by_proxy, ErrorCode::ProxyVoteLocked);
 
+    if voter_record.voted {
         if voter_record.vote {
             proposal_account.yes = proposal_account.yes.checked_sub(voter_record.voting_power).unwrap();
         } else {
@@ -326,79 +609,185 @@ pub fn vote_as_proxy(ctx: Context<VoteAsProxy>, vote_yes: bool) -> Result<()> {
     // Security: Validate delegation
     let delegation_record = &ctx.accounts.delegation_record;
     require!(delegation_record.delegator == ctx.accounts.delegator_user.key(), ErrorCode::Unauthorized);
-    require!(delegation_record.delegate_target == ctx.accounts.proxy_authority.key(), ErrorCode::Unauthorized);
+    let share_bps = delegate_share_bps(delegation_record, &ctx.accounts.proxy_authority.key())
+        .ok_or(ErrorCode::Unauthorized)?;
 
     let proposal_account = &mut ctx.accounts.proposal_account;
     let voter_record = &mut ctx.accounts.voter_record;
     let clock = Clock::get()?;
 
-    require!(proposal_account.is_active, ErrorCode::ProposalNotActive);
-    require!(clock.unix_timestamp <= proposal_account.deadline, ErrorCode::ProposalExpired);
+    require_proposal_open(proposal_account, clock.unix_timestamp)?;
+    require_delegation_covers(delegation_record, proposal_account, clock.unix_timestamp)?;
+
+    // A direct vote (cast before delegating, or as an override) stands
+    require!(!voter_record.voted && !voter_record.overridden, ErrorCode::DirectVoteExists);
    
     // Calculate delegator's voting power
-    let liquid_amount = ctx.accounts.delegator_token_account.amount;
-    let liquid_power = (liquid_amount as f64).sqrt() as u64;
+    let positions = load_stake_positions(ctx.remaining_accounts, &ctx.accounts.delegator_user.key(), ctx.program_id)?;
+    let power = calculate_voting_power(
+        ctx.accounts.delegator_token_account.amount,
+        &positions,
+        clock.unix_timestamp,
+    );
+    require!(power.total > 0, ErrorCode::NoVotingPower);
+    reserve_stake_positions(
+        ctx.remaining_accounts,
+        &ctx.accounts.delegator_user.key(),
+        proposal_account.deadline,
+        clock.unix_timestamp,
+        ctx.program_id,
+    )?;
+
+    let share_power = apply_proxy_vote(
+        proposal_account,
+        voter_record,
+        ctx.accounts.delegator_user.key(),
+        ctx.accounts.proxy_authority.key(),
+        share_bps,
+        vote_yes,
+        &power,
+    )?;
 
-    let (staked_amount, multiplier) = if let Some(stake_record) = &ctx.accounts.delegator_stake_record {
-        (stake_record.staked_amount, stake_record.multiplier)
-    } else {
-        (0, 1)
-    };
+    // Update Proxy User Stats (the person doing the work gets the points)
+    award_vote_points(&mut ctx.accounts.user_stats, ctx.accounts.proxy_authority.key(), clock.unix_timestamp, 1);
+    record_proxy_participation(
+        &mut ctx.accounts.delegate_profile,
+        &mut ctx.accounts.participation,
+        proposal_account.key(),
+        clock.unix_timestamp,
+    );
 
-    let staked_sqrt = (staked_amount as f64).sqrt() as u64;
-    let staked_power = staked_sqrt.checked_mul(multiplier).unwrap_or(0);
-    let total_voting_power = liquid_power.checked_add(staked_power).unwrap();
+    emit!(VoteCast {
+        voter: ctx.accounts.delegator_user.key(),
+        proposal: proposal_account.key(),
+        amount: power.staked_amount,
+        lock_duration: power.lock_duration,
+        voting_power: share_power,
+        multiplier: power.multiplier,
+    });
 
-    require!(total_voting_power > 0, ErrorCode::NoVotingPower);
+    Ok(())
+}
 
-    // Apply vote
-    if voter_record.voted {
-         require!(voter_record.vote != vote_yes, ErrorCode::AlreadyVoted);
-         
-         if voter_record.vote {
-             proposal_account.yes = proposal_account.yes.checked_sub(voter_record.voting_power).unwrap();
-         } else {
-             proposal_account.no = proposal_account.no.checked_sub(voter_record.voting_power).unwrap();
-         }
-
-         if vote_yes {
-             proposal_account.yes = proposal_account.yes.checked_add(total_voting_power).unwrap();
-         } else {
-             proposal_account.no = proposal_account.no.checked_add(total_voting_power).unwrap();
-         }
-    } else {
-         if vote_yes {
-             proposal_account.yes = proposal_account.yes.checked_add(total_voting_power).unwrap();
-         } else {
-             proposal_account.no = proposal_account.no.checked_add(total_voting_power).unwrap();
-         }
-    }
+/// Cast proxy votes for many delegators on one proposal. Every delegation
+/// is validated as in `vote_as_proxy` and the whole batch fails on the
+/// first invalid tuple. `ProxyBatchProcessed` reports how many delegators
+/// were processed so clients can page through large sets.
+pub fn vote_as_proxy_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VoteAsProxyBatch<'info>>,
+    vote_yes: bool,
+    position_account_counts: Vec<u8>,
+) -> Result<()> {
+    let global_account = &ctx.accounts.global_account;
+    require!(global_account.system_enabled, ErrorCode::CircuitBreakerTripped);
 
-    voter_record.proposal = proposal_account.key();
-    voter_record.voter = ctx.accounts.delegator_user.key();
-    voter_record.vote = vote_yes;
-    voter_record.voted = true;
-    voter_record.voting_power = total_voting_power;
-    voter_record.staked_amount = staked_amount;
-    voter_record.voted_by_proxy = true;
+    // Security: Validate delegate
+    require!(ctx.accounts.delegate_profile.is_active, ErrorCode::InvalidDelegate);
+    require!(ctx.accounts.delegate_profile.authority == ctx.accounts.proxy_authority.key(), ErrorCode::Unauthorized);
 
-    // Update Proxy User Stats (the person doing the work gets the points)
-    let user_stats = &mut ctx.accounts.user_stats;
-    if user_stats.proposal_count == 0 {
-        user_stats.user = ctx.accounts.proxy_authority.key();
-        user_stats.score = 0;
+    require!(
+        !position_account_counts.is_empty() && position_account_counts.len() <= MAX_BATCH_VOTES,
+        ErrorCode::InvalidBatchSize
+    );
+    let expected_accounts = position_account_counts
+        .iter()
+        .map(|count| 3 + *count as usize)
+        .sum::<usize>();
+    require!(ctx.remaining_accounts.len() == expected_accounts, ErrorCode::InvalidBatchAccounts);
+
+    let clock = Clock::get()?;
+    require_proposal_open(&ctx.accounts.proposal_account, clock.unix_timestamp)?;
+
+    let proposal_key = ctx.accounts.proposal_account.key();
+    let proxy_authority = ctx.accounts.proxy_authority.key();
+    let payer = ctx.accounts.proxy_authority.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let mut accounts = ctx.remaining_accounts;
+    let mut total_power: u64 = 0;
+
+    for count in position_account_counts.iter() {
+        let (tuple, rest) = accounts.split_at(3 + *count as usize);
+        accounts = rest;
+        let (delegation_record_info, token_account_info) = (&tuple[0], &tuple[1]);
+        let position_accounts = &tuple[2..tuple.len() - 1];
+        let voter_record_info = &tuple[tuple.len() - 1];
+
+        // Security: Validate delegation
+        let delegation_record = Account::<DelegationRecord>::try_from(delegation_record_info)?;
+        let delegator = delegation_record.delegator;
+        let (expected, _) = Pubkey::find_program_address(&[DELEGATION_RECORD_SEED, delegator.as_ref()], ctx.program_id);
+        require_keys_eq!(delegation_record_info.key(), expected, ErrorCode::InvalidBatchAccounts);
+        let share_bps = delegate_share_bps(&delegation_record, &proxy_authority).ok_or(ErrorCode::Unauthorized)?;
+        require_delegation_covers(&delegation_record, &ctx.accounts.proposal_account, clock.unix_timestamp)?;
+
+        let token_account = InterfaceAccount::<TokenAccount>::try_from(token_account_info)?;
+        require!(
+            token_account.mint == global_account.token_mint && token_account.owner == delegator,
+            ErrorCode::InvalidTokenAccount
+        );
+
+        let mut voter_record = load_or_init_voter_record(
+            voter_record_info,
+            &proposal_key,
+            &delegator,
+            &payer,
+            &system_program,
+            ctx.program_id,
+        )?;
+
+        // A direct vote (cast before delegating, or as an override) stands
+        require!(!voter_record.voted && !voter_record.overridden, ErrorCode::DirectVoteExists);
+
+        // Calculate delegator's voting power
+        let positions = load_stake_positions(position_accounts, &delegator, ctx.program_id)?;
+        let power = calculate_voting_power(token_account.amount, &positions, clock.unix_timestamp);
+        require!(power.total > 0, ErrorCode::NoVotingPower);
+        reserve_stake_positions(
+            position_accounts,
+            &delegator,
+            ctx.accounts.proposal_account.deadline,
+            clock.unix_timestamp,
+            ctx.program_id,
+        )?;
+
+        let share_power = apply_proxy_vote(
+            &mut ctx.accounts.proposal_account,
+            &mut voter_record,
+            delegator,
+            proxy_authority,
+            share_bps,
+            vote_yes,
+            &power,
+        )?;
+        voter_record.exit(ctx.program_id)?;
+        total_power = total_power.checked_add(share_power).unwrap();
+
+        emit!(VoteCast {
+            voter: delegator,
+            proposal: proposal_key,
+            amount: power.staked_amount,
+            lock_duration: power.lock_duration,
+            voting_power: share_power,
+            multiplier: power.multiplier,
+        });
     }
-    user_stats.proposal_count = user_stats.proposal_count.checked_add(1).unwrap();
-    user_stats.last_vote_time = clock.unix_timestamp;
-    user_stats.score = user_stats.score.checked_add(10).unwrap();
 
-    emit!(VoteCast {
-        voter: ctx.accounts.delegator_user.key(),
-        proposal: proposal_account.key(),
-        amount: staked_amount,
-        lock_duration: if let Some(r) = &ctx.accounts.delegator_stake_record { r.original_lock_days } else { 0 },
-        voting_power: total_voting_power,
-        multiplier: multiplier,
+    let processed = position_account_counts.len() as u16;
+
+    // Update Proxy User Stats (the person doing the work gets the points)
+    award_vote_points(&mut ctx.accounts.user_stats, proxy_authority, clock.unix_timestamp, processed as u64);
+    record_proxy_participation(
+        &mut ctx.accounts.delegate_profile,
+        &mut ctx.accounts.participation,
+        proposal_key,
+        clock.unix_timestamp,
+    );
+
+    emit!(ProxyBatchProcessed {
+        delegate: proxy_authority,
+        proposal: proposal_key,
+        processed,
+        voting_power: total_power,
     });
 
     Ok(())
@@ -415,7 +804,11 @@ pub fn withdraw_as_proxy(ctx: Context<WithdrawAsProxy>) -> Result<()> {
     // Security: Validate delegation
     let delegation_record = &ctx.accounts.delegation_record;
     require!(delegation_record.delegator == ctx.accounts.delegator_user.key(), ErrorCode::Unauthorized);
-    require!(delegation_record.delegate_target == ctx.accounts.proxy_authority.key(), ErrorCode::Unauthorized);
+    let proxy_authority = ctx.accounts.proxy_authority.key();
+    require!(
+        delegate_share_bps(delegation_record, &proxy_authority).is_some(),
+        ErrorCode::Unauthorized
+    );
 
     let proposal_account = &mut ctx.accounts.proposal_account;
     let voter_record = &mut ctx.accounts.voter_record;
@@ -424,17 +817,429 @@ pub fn withdraw_as_proxy(ctx: Context<WithdrawAsProxy>) -> Result<()> {
     require!(proposal_account.is_active, ErrorCode::ProposalNotActive);
     require!(clock.unix_timestamp <= proposal_account.deadline, ErrorCode::ProposalExpired);
 
+    // Only this delegate's own share vote is withdrawn
+    if let Some(index) = voter_record
+        .proxy_votes
+        .iter()
+        .position(|proxy_vote| proxy_vote.delegate == proxy_authority)
+    {
+        let proxy_vote = voter_record.proxy_votes.remove(index);
+        remove_vote_weight(proposal_account, proxy_vote.vote, proxy_vote.voting_power);
+        voter_record.voted_by_proxy = !voter_record.proxy_votes.is_empty();
+    }
+
+    Ok(())
+}
+
+pub fn clear_proxy_votes(ctx: Context<ClearProxyVotes>) -> Result<()> {
+    let global_account = &ctx.accounts.global_account;
+    require!(global_account.system_enabled, ErrorCode::CircuitBreakerTripped);
+
+    // Votes from a delegate in good standing are not touched
+    let profile_info = &ctx.accounts.delegate_profile;
+    if !profile_info.data_is_empty() {
+        let delegate_profile = DelegateProfile::try_deserialize(&mut &profile_info.try_borrow_data()?[..])?;
+        require!(!delegate_profile.is_active, ErrorCode::DelegateNotSuspended);
+    }
+
+    let proposal_account = &mut ctx.accounts.proposal_account;
+    let voter_record = &mut ctx.accounts.voter_record;
+    let delegate = ctx.accounts.delegate.key();
+
+    require_proposal_open(proposal_account, Clock::get()?.unix_timestamp)?;
+
+    let index = voter_record
+        .proxy_votes
+        .iter()
+        .position(|proxy_vote| proxy_vote.delegate == delegate)
+        .ok_or(ErrorCode::NoProxyVote)?;
+    let proxy_vote = voter_record.proxy_votes.remove(index);
+    remove_vote_weight(proposal_account, proxy_vote.vote, proxy_vote.voting_power);
+    voter_record.voted_by_proxy = !voter_record.proxy_votes.is_empty();
+
+    emit!(ProxyVotesCleared {
+        delegate,
+        delegator: voter_record.voter,
+        proposal: proposal_account.key(),
+        removed_power: proxy_vote.voting_power,
+    });
+
+    Ok(())
+}
+
+
+////////////////////////////////////////////////////////////////
+//                      VOTING HELPERS
+////////////////////////////////////////////////////////////////
+
+/// Breakdown of a voter's hybrid voting power.
+pub struct VotingPower {
+    pub total: u64,
+    pub staked_amount: u64,
+    pub multiplier: u64,
+    pub lock_duration: i64,
+}
+
+/// Hybrid quadratic power at time `at`:
+/// `sqrt(liquid) + sqrt(staked) * ve_multiplier(remaining lock)`.
+/// Positions are pooled before the square root (with an amount-weighted
+/// multiplier), so splitting a stake across positions cannot inflate power.
+pub fn calculate_voting_power(liquid_amount: u64, positions: &[VoterStakeRecord], at: i64) -> VotingPower {
+    let liquid_power = (liquid_amount as f64).sqrt() as u64;
+
+    let mut staked_amount: u64 = 0;
+    let mut weighted_multiplier: u128 = 0;
+    let mut lock_duration: i64 = 0;
+    for position in positions {
+        staked_amount = staked_amount.checked_add(position.staked_amount).unwrap();
+        weighted_multiplier += position.staked_amount as u128 * voting_multiplier_bps(position, at) as u128;
+        lock_duration = lock_duration.max(position.original_lock_days);
+    }
+
+    let multiplier = if staked_amount > 0 {
+        (weighted_multiplier / staked_amount as u128) as u64
+    } else {
+        MULTIPLIER_BPS
+    };
+
+    let staked_sqrt = (staked_amount as f64).sqrt() as u64;
+    let staked_power = (staked_sqrt as u128 * multiplier as u128 / MULTIPLIER_BPS as u128) as u64;
+
+    VotingPower {
+        total: liquid_power.checked_add(staked_power).unwrap(),
+        staked_amount,
+        multiplier,
+        lock_duration,
+    }
+}
+
+fn require_proposal_open(proposal_account: &ProposalAccount, now: i64) -> Result<()> {
+    require!(proposal_account.is_active, ErrorCode::ProposalNotActive);
+    require!(now <= proposal_account.deadline, ErrorCode::ProposalExpired);
+    Ok(())
+}
+
+fn add_vote_weight(proposal_account: &mut ProposalAccount, vote_yes: bool, weight: u64) {
+    if vote_yes {
+        proposal_account.yes = proposal_account.yes.checked_add(weight).unwrap();
+    } else {
+        proposal_account.no = proposal_account.no.checked_add(weight).unwrap();
+    }
+}
+
+fn remove_vote_weight(proposal_account: &mut ProposalAccount, vote_yes: bool, weight: u64) {
+    if vote_yes {
+        proposal_account.yes = proposal_account.yes.checked_sub(weight).unwrap();
+    } else {
+        proposal_account.no = proposal_account.no.checked_sub(weight).unwrap();
+    }
+}
+
+/// Records a direct (non-proxy) vote, switching sides if the voter has
+/// already voted on this proposal.
+fn apply_direct_vote(
+    proposal_account: &mut Account<ProposalAccount>,
+    voter_record: &mut VoterRecord,
+    voter: Pubkey,
+    vote_yes: bool,
+    power: &VotingPower,
+) -> Result<()> {
+    // Delegators keep the final say: a direct vote replaces every proxy vote
+    // on this proposal and blocks further ones, even after it is withdrawn
+    if voter_record.voted_by_proxy {
+        let overridden_power = voter_record
+            .proxy_votes
+            .iter()
+            .fold(0u64, |total, proxy_vote| total.checked_add(proxy_vote.voting_power).unwrap());
+        for proxy_vote in voter_record.proxy_votes.drain(..) {
+            remove_vote_weight(proposal_account, proxy_vote.vote, proxy_vote.voting_power);
+        }
+        voter_record.voted_by_proxy = false;
+
+        emit!(ProxyVoteOverridden {
+            delegator: voter,
+            proposal: proposal_account.key(),
+            overridden_power,
+        });
+    }
+
     if voter_record.voted {
-        if voter_record.vote {
-            proposal_account.yes = proposal_account.yes.checked_sub(voter_record.voting_power).unwrap();
+        // Prevent voting for same option (must switch)
+        require!(voter_record.vote != vote_yes, ErrorCode::AlreadyVoted);
+
+        remove_vote_weight(proposal_account, voter_record.vote, voter_record.voting_power);
+    } else {
+        voter_record.proposal = proposal_account.key();
+        voter_record.voter = voter;
+        voter_record.voted = true;
+    }
+
+    add_vote_weight(proposal_account, vote_yes, power.total);
+
+    voter_record.vote = vote_yes;
+    voter_record.voting_power = power.total;
+    voter_record.staked_amount = power.staked_amount;
+    voter_record.voted_by_proxy = false;
+    voter_record.overridden = true;
+
+    Ok(())
+}
+
+/// Record `proxy`'s vote with its share of the delegator's power, switching
+/// an earlier vote by the same delegate. Returns the power applied.
+fn apply_proxy_vote(
+    proposal_account: &mut Account<ProposalAccount>,
+    voter_record: &mut VoterRecord,
+    delegator: Pubkey,
+    proxy: Pubkey,
+    share_bps: u16,
+    vote_yes: bool,
+    power: &VotingPower,
+) -> Result<u64> {
+    // Only this delegate's share of the delegator's power
+    let share_power = (power.total as u128 * share_bps as u128 / MAX_BPS as u128) as u64;
+
+    // Apply vote
+    match voter_record.proxy_votes.iter_mut().find(|proxy_vote| proxy_vote.delegate == proxy) {
+        Some(proxy_vote) => {
+            require!(proxy_vote.vote != vote_yes, ErrorCode::AlreadyVoted);
+            remove_vote_weight(proposal_account, proxy_vote.vote, proxy_vote.voting_power);
+            proxy_vote.vote = vote_yes;
+            proxy_vote.voting_power = share_power;
+        }
+        None => voter_record.proxy_votes.push(ProxyVote {
+            delegate: proxy,
+            vote: vote_yes,
+            voting_power: share_power,
+        }),
+    }
+    add_vote_weight(proposal_account, vote_yes, share_power);
+
+    voter_record.proposal = proposal_account.key();
+    voter_record.voter = delegator;
+    voter_record.staked_amount = power.staked_amount;
+    voter_record.voted_by_proxy = true;
+
+    Ok(share_power)
+}
+
+/// 10 points per vote cast.
+fn award_vote_points(user_stats: &mut UserStats, user: Pubkey, now: i64, votes: u64) {
+    if user_stats.proposal_count == 0 {
+        user_stats.user = user;
+        user_stats.score = 0;
+    }
+    user_stats.proposal_count = user_stats.proposal_count.checked_add(votes).unwrap();
+    user_stats.last_vote_time = now;
+    user_stats.score = user_stats.score.checked_add(votes.checked_mul(10).unwrap()).unwrap();
+}
+
+/// Validates and applies a single `vote_batch` entry, returning the
+/// proposal's deadline. The proposal tally is only written back once every
+/// check passes, but a missing `VoterRecord` is created (and its rent paid)
+/// before the vote is applied, so a failed entry can still leave an empty
+/// record behind.
+#[allow(clippy::too_many_arguments)]
+fn cast_batch_vote<'info>(
+    proposal_info: &'info AccountInfo<'info>,
+    voter_record_info: &'info AccountInfo<'info>,
+    entry: &BatchVoteEntry,
+    power: &VotingPower,
+    now: i64,
+    user: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<i64> {
+    require_keys_eq!(proposal_info.key(), entry.proposal, ErrorCode::InvalidBatchAccounts);
+    require!(proposal_info.is_writable, ErrorCode::InvalidBatchAccounts);
+
+    let mut proposal_account = Account::<ProposalAccount>::try_from(proposal_info)?;
+    require_proposal_open(&proposal_account, now)?;
+
+    let mut voter_record = load_or_init_voter_record(
+        voter_record_info,
+        &proposal_info.key(),
+        &user.key(),
+        user,
+        system_program,
+        program_id,
+    )?;
+
+    apply_direct_vote(&mut proposal_account, &mut voter_record, user.key(), entry.vote_yes, power)?;
+
+    proposal_account.exit(program_id)?;
+    voter_record.exit(program_id)?;
+
+    emit!(VoteCast {
+        voter: user.key(),
+        proposal: proposal_info.key(),
+        amount: power.staked_amount,
+        lock_duration: power.lock_duration,
+        voting_power: power.total,
+        multiplier: power.multiplier,
+    });
+
+    Ok(proposal_account.deadline)
+}
+
+/// `init_if_needed` for a `VoterRecord` handed in through remaining_accounts.
+fn load_or_init_voter_record<'info>(
+    voter_record_info: &'info AccountInfo<'info>,
+    proposal: &Pubkey,
+    voter: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<Account<'info, VoterRecord>> {
+    let (expected, bump) = Pubkey::find_program_address(
+        &[VOTER_RECORD_SEED, proposal.as_ref(), voter.as_ref()],
+        program_id,
+    );
+    require_keys_eq!(voter_record_info.key(), expected, ErrorCode::InvalidBatchAccounts);
+    require!(voter_record_info.is_writable, ErrorCode::InvalidBatchAccounts);
+
+    if voter_record_info.data_is_empty() {
+        let signer_seeds: &[&[u8]] = &[VOTER_RECORD_SEED, proposal.as_ref(), voter.as_ref(), &[bump]];
+        let rent_exempt = Rent::get()?.minimum_balance(VOTER_RECORD_SPACE);
+        let current_lamports = voter_record_info.lamports();
+
+        if current_lamports == 0 {
+            system_program::create_account(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    system_program::CreateAccount {
+                        from: payer.clone(),
+                        to: voter_record_info.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                rent_exempt,
+                VOTER_RECORD_SPACE as u64,
+                program_id,
+            )?;
         } else {
-            proposal_account.no = proposal_account.no.checked_sub(voter_record.voting_power).unwrap();
+            // Someone pre-funded the address: top up, then allocate and assign.
+            let top_up = rent_exempt.saturating_sub(current_lamports);
+            if top_up > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        system_program::Transfer {
+                            from: payer.clone(),
+                            to: voter_record_info.clone(),
+                        },
+                    ),
+                    top_up,
+                )?;
+            }
+            system_program::allocate(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    system_program::Allocate {
+                        account_to_allocate: voter_record_info.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                VOTER_RECORD_SPACE as u64,
+            )?;
+            system_program::assign(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    system_program::Assign {
+                        account_to_assign: voter_record_info.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                program_id,
+            )?;
         }
 
-        voter_record.voted = false;
-        voter_record.voting_power = 0;
-        voter_record.voted_by_proxy = false;
+        let empty_record = VoterRecord {
+            proposal: *proposal,
+            voter: *voter,
+            vote: false,
+            voted: false,
+            voting_power: 0,
+            staked_amount: 0,
+            voted_by_proxy: false,
+            proxy_votes: Vec::new(),
+            overridden: false,
+        };
+        let mut data = voter_record_info.try_borrow_mut_data()?;
+        empty_record.try_serialize(&mut &mut data[..])?;
     }
 
+    Account::<VoterRecord>::try_from(voter_record_info)
+}
+
+/// Message a voter signs for `vote_with_signature`:
+/// `program_id (32) | proposal (32) | vote_yes (1) | nonce (8, LE) | expiry (8, LE)`.
+pub fn signed_vote_message(
+    program_id: &Pubkey,
+    proposal: &Pubkey,
+    vote_yes: bool,
+    nonce: u64,
+    expiry: i64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + 32 + 1 + 8 + 8);
+    message.extend_from_slice(program_id.as_ref());
+    message.extend_from_slice(proposal.as_ref());
+    message.push(vote_yes as u8);
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message.extend_from_slice(&expiry.to_le_bytes());
+    message
+}
+
+/// Checks that the instruction right before this one is an ed25519
+/// precompile call verifying `message` against `signer`. The precompile
+/// has already validated the signature itself; here we make sure it was
+/// over the data we expect, with every offset pointing into its own data.
+fn verify_ed25519_signature(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    // Ed25519 instruction layout: count (1) + padding (1) + offsets (14 per signature)
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    const THIS_INSTRUCTION: u16 = u16::MAX;
+
+    let current_index = load_current_index_checked(instructions)?;
+    require!(current_index > 0, ErrorCode::InvalidVoteSignature);
+
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions)?;
+    require_keys_eq!(ed25519_ix.program_id, ed25519_program::ID, ErrorCode::InvalidVoteSignature);
+
+    let data = &ed25519_ix.data;
+    require!(data.len() >= OFFSETS_START + OFFSETS_LEN, ErrorCode::InvalidVoteSignature);
+    require!(data[0] == 1, ErrorCode::InvalidVoteSignature);
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = OFFSETS_START;
+    let signature_ix = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix = read_u16(offsets + 12);
+
+    require!(
+        signature_ix == THIS_INSTRUCTION && pubkey_ix == THIS_INSTRUCTION && message_ix == THIS_INSTRUCTION,
+        ErrorCode::InvalidVoteSignature
+    );
+
+    let signed_pubkey = data.get(pubkey_offset..pubkey_offset + 32).ok_or(ErrorCode::InvalidVoteSignature)?;
+    require!(signed_pubkey == signer.as_ref(), ErrorCode::InvalidVoteSignature);
+
+    let signed_message = data.get(message_offset..message_offset + message_size).ok_or(ErrorCode::InvalidVoteSignature)?;
+    require!(signed_message == message, ErrorCode::InvalidVoteSignature);
+
     Ok(())
 }
+
+fn error_code_of(err: Error) -> u32 {
+    match ProgramError::from(err) {
+        ProgramError::Custom(code) => code,
+        other => u64::from(other) as u32,
+    }
+}
@@ -2,11 +2,13 @@
 
 use anchor_lang::prelude::*;
 
+pub mod constants;
 pub mod error;
 pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::DelegateShare;
 
 declare_id!("EE1i9YyUyjEKxXNzRaup86EkCDyd1bt21e1ecF7rgN9R");
 
@@ -27,17 +29,80 @@ pub mod pulsar_dao {
         instructions::admin::admin_mint(ctx, amount)
     }
 
+    pub fn toggle_emergency_exit(ctx: Context<ToggleEmergencyExit>) -> Result<()> {
+        instructions::admin::toggle_emergency_exit(ctx)
+    }
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        instructions::admin::initialize_config(ctx, params)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        instructions::admin::update_config(ctx, params)
+    }
+
     // staking
     pub fn initialize_stake(ctx: Context<InitializeStake>) -> Result<()> {
         instructions::staking::initialize_stake(ctx)
     }
 
-    pub fn deposit_tokens(ctx: Context<DepositTokens>, amount: u64, lock_days: i64) -> Result<()> {
-        instructions::staking::deposit_tokens(ctx, amount, lock_days)
+    pub fn deposit_tokens(ctx: Context<DepositTokens>, position_id: u64, amount: u64, lock_days: i64) -> Result<()> {
+        instructions::staking::deposit_tokens(ctx, position_id, amount, lock_days)
+    }
+
+    pub fn request_unstake(ctx: Context<RequestUnstake>, position_id: u64, amount: u64) -> Result<()> {
+        instructions::staking::request_unstake(ctx, position_id, amount)
+    }
+
+    pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>, position_id: u64) -> Result<()> {
+        instructions::staking::withdraw_unstaked(ctx, position_id)
+    }
+
+    pub fn early_unstake(ctx: Context<EarlyUnstake>, position_id: u64) -> Result<()> {
+        instructions::staking::early_unstake(ctx, position_id)
+    }
+
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>, position_id: u64) -> Result<()> {
+        instructions::staking::emergency_withdraw(ctx, position_id)
+    }
+
+    pub fn tokenize_position(ctx: Context<TokenizePosition>, position_id: u64) -> Result<()> {
+        instructions::staking::tokenize_position(ctx, position_id)
+    }
+
+    pub fn extend_lock(ctx: Context<ExtendLock>, position_id: u64, lock_days: i64) -> Result<()> {
+        instructions::staking::extend_lock(ctx, position_id, lock_days)
     }
 
-    pub fn unstake_tokens(ctx: Context<UnstakeTokens>) -> Result<()> {
-        instructions::staking::unstake_tokens(ctx)
+    pub fn expire_lock(ctx: Context<ExpireLock>, position_id: u64) -> Result<()> {
+        instructions::staking::expire_lock(ctx, position_id)
+    }
+
+    // staking rewards
+    pub fn initialize_reward_pool(ctx: Context<InitializeRewardPool>, reward_rate: u64) -> Result<()> {
+        instructions::rewards::initialize_reward_pool(ctx, reward_rate)
+    }
+
+    pub fn set_reward_rate(ctx: Context<SetRewardRate>, reward_rate: u64) -> Result<()> {
+        instructions::rewards::set_reward_rate(ctx, reward_rate)
+    }
+
+    pub fn fund_reward_pool(ctx: Context<FundRewardPool>, amount: u64) -> Result<()> {
+        instructions::rewards::fund_reward_pool(ctx, amount)
+    }
+
+    pub fn claim_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimRewards<'info>>,
+        position_id: u64,
+    ) -> Result<()> {
+        instructions::rewards::claim_rewards(ctx, position_id)
+    }
+
+    pub fn compound_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompoundRewards<'info>>,
+        position_id: u64,
+    ) -> Result<()> {
+        instructions::rewards::compound_rewards(ctx, position_id)
     }
 
     // proposal
@@ -45,11 +110,13 @@ pub mod pulsar_dao {
         ctx: Context<CreateProposal>, 
         title: String, 
         description: String, 
-        deadline: i64
+        deadline: i64,
+        emergency: bool
     ) -> Result<()> {
-        instructions::proposal::create_proposal(ctx, title, description, deadline)
+        instructions::proposal::create_proposal(ctx, title, description, deadline, emergency)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_treasury_proposal(
         ctx: Context<CreateTreasuryProposal>,
         title: String,
@@ -58,6 +125,7 @@ pub mod pulsar_dao {
         transfer_amount: u64,
         transfer_destination: Pubkey,
         timelock_seconds: i64,
+        emergency: bool,
     ) -> Result<()> {
         instructions::proposal::create_treasury_proposal(
             ctx, 
@@ -66,7 +134,8 @@ pub mod pulsar_dao {
             deadline, 
             transfer_amount, 
             transfer_destination, 
-            timelock_seconds
+            timelock_seconds,
+            emergency
         )
     }
 
@@ -75,6 +144,23 @@ pub mod pulsar_dao {
         instructions::voting::vote(ctx, vote_yes)
     }
 
+    pub fn vote_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VoteBatch<'info>>,
+        entries: Vec<BatchVoteEntry>,
+        best_effort: bool,
+    ) -> Result<()> {
+        instructions::voting::vote_batch(ctx, entries, best_effort)
+    }
+
+    pub fn vote_with_signature(
+        ctx: Context<VoteWithSignature>,
+        vote_yes: bool,
+        nonce: u64,
+        expiry: i64,
+    ) -> Result<()> {
+        instructions::voting::vote_with_signature(ctx, vote_yes, nonce, expiry)
+    }
+
     pub fn withdraw_vote(ctx: Context<WithdrawVote>) -> Result<()> {
         instructions::voting::withdraw_vote(ctx)
     }
@@ -83,10 +169,22 @@ pub mod pulsar_dao {
         instructions::voting::vote_as_proxy(ctx, vote_yes)
     }
 
+    pub fn vote_as_proxy_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VoteAsProxyBatch<'info>>,
+        vote_yes: bool,
+        position_account_counts: Vec<u8>,
+    ) -> Result<()> {
+        instructions::voting::vote_as_proxy_batch(ctx, vote_yes, position_account_counts)
+    }
+
     pub fn withdraw_as_proxy(ctx: Context<WithdrawAsProxy>) -> Result<()> {
         instructions::voting::withdraw_as_proxy(ctx)
     }
 
+    pub fn clear_proxy_votes(ctx: Context<ClearProxyVotes>) -> Result<()> {
+        instructions::voting::clear_proxy_votes(ctx)
+    }
+
     // treasury execution
     pub fn execute_proposal(ctx: Context<ExecuteProposal>, proposal_number: u64) -> Result<()> {
         instructions::treasury::execute_proposal(ctx, proposal_number)
@@ -101,18 +199,79 @@ pub mod pulsar_dao {
         instructions::delegation::register_delegate(ctx)
     }
 
-    pub fn delegate_vote(ctx: Context<DelegateVote>) -> Result<()> {
-        instructions::delegation::delegate_vote(ctx)
+    pub fn nominate_delegate(ctx: Context<NominateDelegate>, metadata: DelegateMetadata) -> Result<()> {
+        instructions::delegation::nominate_delegate(ctx, metadata)
+    }
+
+    pub fn update_delegate_metadata(ctx: Context<UpdateDelegateMetadata>, metadata: DelegateMetadata) -> Result<()> {
+        instructions::delegation::update_delegate_metadata(ctx, metadata)
+    }
+
+    pub fn delegate_vote(ctx: Context<DelegateVote>, terms: DelegationTerms) -> Result<()> {
+        instructions::delegation::delegate_vote(ctx, terms)
+    }
+
+    pub fn delegate_split(ctx: Context<DelegateSplit>, shares: Vec<DelegateShare>, terms: DelegationTerms) -> Result<()> {
+        instructions::delegation::delegate_split(ctx, shares, terms)
     }
 
-    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+    pub fn revoke_delegation<'info>(ctx: Context<'_, '_, 'info, 'info, RevokeDelegation<'info>>) -> Result<()> {
         instructions::delegation::revoke_delegation(ctx)
     }
 
+    pub fn record_missed_proposal(ctx: Context<RecordMissedProposal>) -> Result<()> {
+        instructions::delegation::record_missed_proposal(ctx)
+    }
+
+    pub fn set_delegate_commission(ctx: Context<SetDelegateCommission>, commission_bps: u16) -> Result<()> {
+        instructions::delegation::set_delegate_commission(ctx, commission_bps)
+    }
+
+    pub fn resync_delegation(ctx: Context<ResyncDelegation>) -> Result<()> {
+        instructions::delegation::resync_delegation(ctx)
+    }
+
+    pub fn suspend_delegate(ctx: Context<SetDelegateStatus>, reason: String) -> Result<()> {
+        instructions::delegation::suspend_delegate(ctx, reason)
+    }
+
+    pub fn reinstate_delegate(ctx: Context<SetDelegateStatus>, reason: String) -> Result<()> {
+        instructions::delegation::reinstate_delegate(ctx, reason)
+    }
+
     pub fn remove_delegate(ctx: Context<RemoveDelegate>) -> Result<()> {
         instructions::delegation::remove_delegate(ctx)
     }
 
+    // council
+    pub fn initialize_council(ctx: Context<InitializeCouncil>, seat_count: u8, term_days: i64) -> Result<()> {
+        instructions::council::initialize_council(ctx, seat_count, term_days)
+    }
+
+    pub fn open_council_election(ctx: Context<OpenCouncilElection>, deadline: i64) -> Result<()> {
+        instructions::council::open_council_election(ctx, deadline)
+    }
+
+    pub fn stand_for_council(ctx: Context<StandForCouncil>) -> Result<()> {
+        instructions::council::stand_for_council(ctx)
+    }
+
+    pub fn vote_council_election(ctx: Context<VoteCouncilElection>, candidate: Pubkey) -> Result<()> {
+        instructions::council::vote_council_election(ctx, candidate)
+    }
+
+    pub fn finalize_council_election(ctx: Context<FinalizeCouncilElection>) -> Result<()> {
+        instructions::council::finalize_council_election(ctx)
+    }
+
+    pub fn veto_proposal(ctx: Context<CouncilProposalAction>) -> Result<()> {
+        instructions::council::veto_proposal(ctx)
+    }
+
+    pub fn fast_track_proposal(ctx: Context<CouncilProposalAction>) -> Result<()> {
+        instructions::council::fast_track_proposal(ctx)
+    }
+
     // gamification / faucet
     pub fn request_tokens(ctx: Context<RequestTokens>) -> Result<()> {
         instructions::gamification::request_tokens(ctx)
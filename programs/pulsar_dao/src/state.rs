@@ -10,6 +10,27 @@ pub struct GlobalAccount {
     pub token_mint: Pubkey,
     pub proposal_count: u64,
     pub system_enabled: bool,
+    pub total_staked: u64,                  // Tokens in stake positions, excluding unbonding
+    pub total_weighted_stake: u64,          // Sum of staked amount x lock multiplier
+    pub staker_count: u64,                  // Owners with at least one non-empty position
+    pub emergency_exit_enabled: bool,       // Stakers may withdraw past their locks (breaker must be tripped)
+}
+
+#[account]
+pub struct DaoConfig {
+    pub early_unstake_penalty_bps: u16,     // Penalty for a full lock still remaining
+    pub burn_early_unstake_penalty: bool,   // Burn the penalty instead of sending it to the treasury vault
+    pub unbonding_period_days: i64,         // Wait between request_unstake and withdraw_unstaked
+    pub max_delegate_commission_bps: u16,   // Cap on the reward commission a delegate may charge
+    pub commission_notice_days: i64,        // Wait before a commission change takes effect
+}
+
+#[account]
+pub struct RewardPool {
+    pub reward_rate: u64,                   // Tokens emitted per second across all stakers
+    pub acc_reward_per_share: u128,         // Scaled by REWARD_PRECISION
+    pub last_update_time: i64,
+    pub total_weighted_stake: u64,
 }
 
 #[account]
@@ -28,6 +49,8 @@ pub struct ProposalAccount {
     pub transfer_destination: Pubkey,       // Destination wallet (SystemProgram if Standard)
     pub timelock_seconds: i64,              // Seconds to wait after deadline before execution
     pub executed: bool,                     // Has been executed?
+    pub vetoed: bool,                       // Vetoed by the council, can never execute
+    pub emergency: bool,                    // Flagged at creation, the only proposals the council can fast-track
 }
 
 #[account]
@@ -38,28 +61,136 @@ pub struct VoterRecord {
     pub voted: bool,
     pub voting_power: u64,
     pub staked_amount: u64,
-    pub voted_by_proxy: bool,
+    pub voted_by_proxy: bool,               // At least one delegate has voted this record's power
+    pub proxy_votes: Vec<ProxyVote>,        // One entry per delegate share that voted
+    pub overridden: bool,                   // Voter has voted directly; survives withdrawal and blocks proxies
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ProxyVote {
+    pub delegate: Pubkey,
+    pub vote: bool,
+    pub voting_power: u64,
+}
+
+#[account]
+pub struct VoteNonce {
+    pub voter: Pubkey,
+    pub next_nonce: u64,
+}
+
+#[account]
+pub struct StakerProfile {
+    pub owner: Pubkey,
+    pub position_count: u64,                // Next position index
+    pub active_positions: u64,              // Positions currently holding tokens
 }
 
 #[account]
 pub struct VoterStakeRecord {
     pub owner: Pubkey,
+    pub position_id: u64,
     pub staked_amount: u64,
     pub lock_end_time: i64,
     pub original_lock_days: i64,
-    pub multiplier: u64,
+    pub multiplier: u64,                    // Lock multiplier in basis points (10_000 = 1x)
+    pub unbonding_amount: u64,              // Requested for withdrawal, no longer counts toward voting power
+    pub unbonding_end_time: i64,
+    pub reward_weight: u64,                 // Weight currently registered with the reward pool
+    pub reward_debt: u128,
+    pub pending_rewards: u64,
+    pub position_mint: Pubkey,              // Position NFT, default if the position is not tokenized
+    pub vote_holder: Pubkey,                // Wallet whose votes this position's power is counted in
+    pub vote_reserved_until: i64,           // Latest deadline among those votes
+    pub pending_commission: u64,            // Delegate commission charged as rewards accrued, paid on claim
 }
 
 #[account]
 pub struct DelegateProfile {
     pub authority: Pubkey,
-    pub is_active: bool,
+    pub is_active: bool,                    // Self-nominated profiles start inactive until approved
+    pub display_name: String,
+    pub platform_uri: String,               // Off-chain platform statement
+    pub platform_hash: [u8; 32],            // Content hash of the platform statement
+    pub focus_tags: Vec<String>,
+    pub delegator_count: u64,               // Live delegations naming this delegate
+    pub delegated_stake: u64,               // Staked tokens delegated, weighted by each delegator's share
+    pub registered_proposal_count: u64,     // Proposal count when the profile was created; later proposals count towards participation
+    pub proposals_voted: u64,               // Proposals with at least one proxy vote
+    pub proposals_missed: u64,              // Proposals closed without a proxy vote
+    pub last_active_time: i64,
+    pub commission_bps: u16,                // Cut of delegators' staking rewards
+    pub pending_commission_bps: u16,        // Replaces commission_bps at commission_effective_at
+    pub commission_effective_at: i64,       // 0 = no change scheduled
+    pub status_reason: String,              // Why the delegate was last suspended or reinstated
+    pub status_updated_at: i64,
+}
+
+#[account]
+pub struct DelegateParticipation {
+    pub delegate: Pubkey,
+    pub proposal: Pubkey,
+    pub voted: bool,                        // False when recorded as missed
 }
 
 #[account]
 pub struct DelegationRecord {
     pub delegator: Pubkey,
-    pub delegate_target: Pubkey,
+    pub shares: Vec<DelegateShare>,         // Basis points of the delegator's power per delegate, summing to 10_000
+    pub delegated_stake: u64,               // Delegator's staked tokens as last credited to the delegates
+    pub expires_at: i64,                    // 0 = never expires
+    pub scope: u32,                         // Bitmask of covered proposal types (bit n = proposal_type n)
+    pub commission_bps: u16,                // Share-weighted commission of the delegates, capped by config
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DelegateShare {
+    pub delegate: Pubkey,
+    pub bps: u16,
+}
+
+#[account]
+pub struct Council {
+    pub seats: Vec<CouncilSeat>,            // Fixed number of seats, default member = never filled
+    pub term_days: i64,                     // Length of a seat's term
+    pub election_count: u64,
+    pub election_open: bool,                // An election is running and not yet finalized
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CouncilSeat {
+    pub member: Pubkey,
+    pub term_end: i64,                      // Seat is vacant once this passes
+}
+
+#[account]
+pub struct CouncilElection {
+    pub number: u64,
+    pub seats_up: u8,                       // Seats vacant or expiring by the deadline
+    pub deadline: i64,
+    pub candidates: Vec<ElectionCandidate>,
+    pub finalized: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ElectionCandidate {
+    pub candidate: Pubkey,
+    pub votes: u64,
+}
+
+#[account]
+pub struct CouncilMotion {
+    pub proposal: Pubkey,
+    pub veto_approvals: Vec<Pubkey>,        // Seated members backing a veto
+    pub fast_track_approvals: Vec<Pubkey>,  // Seated members backing a fast-track
+}
+
+#[account]
+pub struct CouncilBallot {
+    pub election: Pubkey,
+    pub voter: Pubkey,
+    pub candidate: Pubkey,
+    pub voting_power: u64,
 }
 
 #[account]
@@ -87,7 +218,85 @@ pub struct VoteCast {
     pub amount: u64,
     pub lock_duration: i64,
     pub voting_power: u64,
-    pub multiplier: u64,
+    pub multiplier: u64,                    // Effective ve multiplier in basis points (10_000 = 1x)
+}
+
+#[event]
+pub struct BatchVoteEntryFailed {
+    pub voter: Pubkey,
+    pub proposal: Pubkey,
+    pub index: u16,
+    pub error_code: u32,
+}
+
+#[event]
+pub struct BatchVoteProcessed {
+    pub voter: Pubkey,
+    pub succeeded: u16,
+    pub failed: u16,
+    pub voting_power: u64,
+}
+
+#[event]
+pub struct DelegateCommissionPaid {
+    pub delegate: Pubkey,
+    pub delegator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ProxyBatchProcessed {
+    pub delegate: Pubkey,
+    pub proposal: Pubkey,
+    pub processed: u16,
+    pub voting_power: u64,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub owner: Pubkey,
+    pub position_id: u64,
+    pub amount: u64,
+    pub unbonding_end_time: i64,
+}
+
+#[event]
+pub struct EmergencyWithdrawn {
+    pub owner: Pubkey,
+    pub position_id: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PositionTokenized {
+    pub owner: Pubkey,
+    pub position_id: u64,
+    pub position_mint: Pubkey,
+}
+
+#[event]
+pub struct StakeWithdrawn {
+    pub owner: Pubkey,
+    pub position_id: u64,
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct EarlyUnstaked {
+    pub owner: Pubkey,
+    pub position_id: u64,
+    pub amount_unbonding: u64,
+    pub penalty: u64,
+    pub penalty_burned: bool,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub owner: Pubkey,
+    pub position_id: u64,
+    pub amount: u64,
+    pub compounded: bool,
 }
 
 #[event]
@@ -99,6 +308,26 @@ pub struct ProposalExecuted {
     pub success: bool,
 }
 
+#[event]
+pub struct CouncilMemberElected {
+    pub member: Pubkey,
+    pub seat: u8,
+    pub term_end: i64,
+}
+
+#[event]
+pub struct ProposalVetoed {
+    pub proposal: Pubkey,
+    pub member: Pubkey,
+}
+
+#[event]
+pub struct ProposalFastTracked {
+    pub proposal: Pubkey,
+    pub member: Pubkey,
+    pub deadline: i64,
+}
+
 #[event]
 pub struct ProposalFundsReclaimed {
     pub proposal: Pubkey,
@@ -106,3 +335,31 @@ pub struct ProposalFundsReclaimed {
     pub amount: u64,
 }
 
+
+#[event]
+pub struct ProxyVoteOverridden {
+    pub delegator: Pubkey,
+    pub proposal: Pubkey,
+    pub overridden_power: u64,
+}
+
+#[event]
+pub struct DelegateStatusChanged {
+    pub delegate: Pubkey,
+    pub is_active: bool,
+    pub reason: String,
+}
+
+#[event]
+pub struct ProxyVotesCleared {
+    pub delegate: Pubkey,
+    pub delegator: Pubkey,
+    pub proposal: Pubkey,
+    pub removed_power: u64,
+}
+
+#[event]
+pub struct DelegationVoided {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+}
@@ -6,6 +6,7 @@ import {
   getOrCreateAssociatedTokenAccount, 
   mintTo, 
   getAssociatedTokenAddress,
+  transfer,
   TOKEN_PROGRAM_ID 
 } from "@solana/spl-token";
 
@@ -26,7 +27,35 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
   
   let globalPDAAddress: anchor.web3.PublicKey;
   let vaultPDAAddress: anchor.web3.PublicKey;
-  let stakeRecordPDA: anchor.web3.PublicKey;
+  let treasuryVaultPDA: anchor.web3.PublicKey;
+  let daoConfigPDA: anchor.web3.PublicKey;
+  let rewardPoolPDA: anchor.web3.PublicKey;
+  let rewardVaultPDA: anchor.web3.PublicKey;
+  let stakerProfilePDA: anchor.web3.PublicKey;
+  let stakeRecordPDA: anchor.web3.PublicKey; // User 1 position #0
+  let user1Positions: anchor.web3.PublicKey[] = [];
+
+  const stakePositionPDA = (user: anchor.web3.PublicKey, positionId: number) => {
+      const buffer = Buffer.alloc(8);
+      buffer.writeBigUInt64LE(BigInt(positionId));
+      return anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("stake_record"), user.toBuffer(), buffer],
+          program.programId
+      )[0];
+  };
+
+  // Stake positions are read-only remaining accounts when voting
+  const positionAccounts = (positions: anchor.web3.PublicKey[]) =>
+      positions.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }));
+  // A delegate's (profile, token account) pair, through which commission is paid
+  const commissionAccounts = (profile: anchor.web3.PublicKey, tokenAccount: anchor.web3.PublicKey) => [
+      { pubkey: profile, isWritable: true, isSigner: false },
+      { pubkey: tokenAccount, isWritable: true, isSigner: false },
+  ];
+  // Revoking before any commission was charged needs no reward vault
+  const noRewardVault = { rewardVault: null, tokenMint: null, tokenProgram: null };
+  // Delegation terms: no expiry, every proposal type
+  const openTerms = { expiresAt: new BN(0), scope: 0xffffffff };
   let proposalPDAAddress: anchor.web3.PublicKey;
   let proposal2PDA: anchor.web3.PublicKey;
 
@@ -70,6 +99,24 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
         program.programId
     );
 
+    [treasuryVaultPDA] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("treasury_vault"), mint.toBuffer()],
+        program.programId
+    );
+    [daoConfigPDA] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("dao_config")],
+        program.programId
+    );
+
+    [rewardPoolPDA] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("reward_pool")],
+        program.programId
+    );
+    [rewardVaultPDA] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("reward_vault"), mint.toBuffer()],
+        program.programId
+    );
+
     // 5. Mint Tokens to Users
     // User 1: 150 Tokens (100 Stake, 50 Liquid)
     user1ATA = (await getOrCreateAssociatedTokenAccount(provider.connection, (owner as any).payer, mint, user1.publicKey)).address;
@@ -104,19 +151,70 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
     expect(state.systemEnabled).to.be.true;
   });
 
+  it("Admin Initializes DAO Config and Treasury Vault", async () => {
+    await program.methods
+      .initializeConfig({
+        earlyUnstakePenaltyBps: 5000,
+        burnEarlyUnstakePenalty: false,
+        unbondingPeriodDays: new BN(2),
+        maxDelegateCommissionBps: 2000,
+        commissionNoticeDays: new BN(0),
+      })
+      .accounts({
+        globalAccount: globalPDAAddress,
+        daoConfig: daoConfigPDA,
+        treasuryVault: treasuryVaultPDA,
+        tokenMint: mint,
+        admin: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const config = await program.account.daoConfig.fetch(daoConfigPDA);
+    expect(config.earlyUnstakePenaltyBps).to.eq(5000);
+    expect(config.burnEarlyUnstakePenalty).to.be.false;
+    expect(config.unbondingPeriodDays.toNumber()).to.eq(2);
+    expect(config.maxDelegateCommissionBps).to.eq(2000);
+  });
+
+  it("Admin Initializes the Staking Reward Pool", async () => {
+    await program.methods
+      .initializeRewardPool(new BN(1))
+      .accounts({
+        globalAccount: globalPDAAddress,
+        rewardPool: rewardPoolPDA,
+        rewardVault: rewardVaultPDA,
+        tokenMint: mint,
+        admin: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    const pool = await program.account.rewardPool.fetch(rewardPoolPDA);
+    expect(pool.rewardRate.toNumber()).to.eq(1);
+    expect(pool.totalWeightedStake.toNumber()).to.eq(0);
+  });
+
   // =========================================================================
   // STAKING LOGIC
   // =========================================================================
 
   it("User 1 Initializes Staking Account", async () => {
-      [stakeRecordPDA] = await anchor.web3.PublicKey.findProgramAddress(
-          [Buffer.from("stake_record"), user1.publicKey.toBuffer()],
+      [stakerProfilePDA] = await anchor.web3.PublicKey.findProgramAddress(
+          [Buffer.from("staker_profile"), user1.publicKey.toBuffer()],
           program.programId
       );
+      stakeRecordPDA = stakePositionPDA(user1.publicKey, 0);
+      user1Positions = [stakeRecordPDA];
 
       await program.methods
         .initializeStake()
         .accounts({
+            stakerProfile: stakerProfilePDA,
             stakeRecord: stakeRecordPDA,
             user: user1.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId,
@@ -127,6 +225,7 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
       const account = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
       expect(account.stakedAmount.toNumber()).to.eq(0);
       expect(account.owner.toString()).to.eq(user1.publicKey.toString());
+      expect(account.positionId.toNumber()).to.eq(0);
   });
 
   it("User 1 Deposits 100 Tokens for 30 days (2x Multiplier)", async () => {
@@ -134,9 +233,11 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
       const lockDays = new BN(30);
 
       await program.methods
-        .depositTokens(amount, lockDays)
+        .depositTokens(new BN(0), amount, lockDays)
         .accounts({
             globalAccount: globalPDAAddress,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
             stakeRecord: stakeRecordPDA,
             vault: vaultPDAAddress,
             tokenMint: mint,
@@ -151,29 +252,192 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
 
       const account = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
       expect(account.stakedAmount.toNumber()).to.eq(100);
-      expect(account.multiplier.toNumber()).to.eq(2); // 1 + 30/30
+      expect(account.multiplier.toNumber()).to.eq(20000); // 2x in basis points
+
+      const global = await program.account.globalAccount.fetch(globalPDAAddress);
+      expect(global.totalStaked.toNumber()).to.eq(100);
+      expect(global.totalWeightedStake.toNumber()).to.eq(200);
+      expect(global.stakerCount.toNumber()).to.eq(1);
+  });
+
+  it("Cannot Stake Tokens of Another Mint", async () => {
+      const fakeMint = await createMint(provider.connection, (owner as any).payer, owner.publicKey, null, 0);
+      const fakeATA = (await getOrCreateAssociatedTokenAccount(
+          provider.connection, (owner as any).payer, fakeMint, user1.publicKey
+      )).address;
+      await mintTo(provider.connection, (owner as any).payer, fakeMint, fakeATA, owner.publicKey, 1000);
+      const [fakeVault] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("vault"), fakeMint.toBuffer()],
+          program.programId
+      );
+
+      try {
+          await program.methods
+            .depositTokens(new BN(0), new BN(1000), new BN(30))
+            .accounts({
+                globalAccount: globalPDAAddress,
+                stakerProfile: stakerProfilePDA,
+                rewardPool: rewardPoolPDA,
+                stakeRecord: stakeRecordPDA,
+                vault: fakeVault,
+                tokenMint: fakeMint,
+                userTokenAccount: fakeATA,
+                user: user1.publicKey,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            })
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have failed with ConstraintRaw");
+      } catch(e) {
+          expect(e.message).to.include("ConstraintRaw");
+      }
+
+      const account = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      expect(account.stakedAmount.toNumber()).to.eq(100);
   });
 
   it("User 1 Fails to Unstake (Tokens Locked)", async () => {
       try {
-          await program.methods.unstakeTokens()
+          await program.methods.requestUnstake(new BN(0), new BN(100))
             .accounts({
+                daoConfig: daoConfigPDA,
+                globalAccount: globalPDAAddress,
+                stakerProfile: stakerProfilePDA,
+                rewardPool: rewardPoolPDA,
                 stakeRecord: stakeRecordPDA,
+                user: user1.publicKey,
+            })
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have failed with TokensLocked");
+      } catch(e) {
+          expect(e.message).to.include("Tokens are still locked");
+      }
+  });
+
+  it("User 1 Opens a Second Position Without Touching the First", async () => {
+      await mintTo(provider.connection, (owner as any).payer, mint, user1ATA, owner.publicKey, 20);
+
+      const secondPositionPDA = stakePositionPDA(user1.publicKey, 1);
+      await program.methods
+        .initializeStake()
+        .accounts({
+            stakerProfile: stakerProfilePDA,
+            stakeRecord: secondPositionPDA,
+            user: user1.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+
+      const firstBefore = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+
+      await program.methods
+        .depositTokens(new BN(1), new BN(20), new BN(90))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: secondPositionPDA,
+            vault: vaultPDAAddress,
+            tokenMint: mint,
+            userTokenAccount: user1ATA,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user1])
+        .rpc();
+      user1Positions.push(secondPositionPDA);
+
+      const profile = await program.account.stakerProfile.fetch(stakerProfilePDA);
+      expect(profile.positionCount.toNumber()).to.eq(2);
+      expect(profile.activePositions.toNumber()).to.eq(2);
+
+      // A second position from the same owner is not a new staker
+      const global = await program.account.globalAccount.fetch(globalPDAAddress);
+      expect(global.totalStaked.toNumber()).to.eq(120);
+      expect(global.stakerCount.toNumber()).to.eq(1);
+
+      const second = await program.account.voterStakeRecord.fetch(secondPositionPDA);
+      expect(second.stakedAmount.toNumber()).to.eq(20);
+      expect(second.multiplier.toNumber()).to.eq(25454); // Interpolated between 2x (30d) and 5x (360d)
+
+      // The first position keeps its own lock
+      const firstAfter = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      expect(firstAfter.lockEndTime.toNumber()).to.eq(firstBefore.lockEndTime.toNumber());
+      expect(firstAfter.multiplier.toNumber()).to.eq(20000);
+  });
+
+  it("User 1 Cannot Shorten a Running Lock on Top-Up (LockDurationDowngrade)", async () => {
+      try {
+          await program.methods
+            .depositTokens(new BN(1), new BN(1), new BN(30))
+            .accounts({
+                globalAccount: globalPDAAddress,
+                stakerProfile: stakerProfilePDA,
+                rewardPool: rewardPoolPDA,
+                stakeRecord: user1Positions[1],
                 vault: vaultPDAAddress,
                 tokenMint: mint,
                 userTokenAccount: user1ATA,
                 user: user1.publicKey,
                 tokenProgram: TOKEN_PROGRAM_ID,
                 systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
             })
             .signers([user1])
             .rpc();
-          expect.fail("Should have failed with TokensLocked");
+          expect.fail("Should have failed with LockDurationDowngrade");
       } catch(e) {
-          expect(e.message).to.include("Tokens are still locked");
+          expect(e.message).to.include("LockDurationDowngrade");
+      }
+  });
+
+  it("Rejects Out-of-Range Lock Durations Before Any Arithmetic", async () => {
+      // Large enough to overflow when converted to seconds
+      for (const lockDays of [new BN(10), new BN("9223372036854775807")]) {
+          try {
+              await program.methods
+                .extendLock(new BN(1), lockDays)
+                .accounts({
+                    globalAccount: globalPDAAddress,
+                    rewardPool: rewardPoolPDA,
+                    stakeRecord: user1Positions[1],
+                    user: user1.publicKey,
+                })
+                .signers([user1])
+                .rpc();
+              expect.fail("Should have failed with InvalidLockDuration");
+          } catch(e) {
+              expect(e.message).to.include("InvalidLockDuration");
+          }
       }
   });
 
+  it("User 1 Extends the Second Position's Lock Without Depositing", async () => {
+      const before = await program.account.voterStakeRecord.fetch(user1Positions[1]);
+
+      await program.methods
+        .extendLock(new BN(1), new BN(180))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: user1Positions[1],
+            user: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      const after = await program.account.voterStakeRecord.fetch(user1Positions[1]);
+      expect(after.stakedAmount.toNumber()).to.eq(before.stakedAmount.toNumber());
+      expect(after.multiplier.toNumber()).to.eq(33636);
+      expect(after.lockEndTime.toNumber()).to.be.greaterThan(before.lockEndTime.toNumber());
+  });
+
   // =========================================================================
   // PROPOSALS & VOTING
   // =========================================================================
@@ -191,10 +455,10 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
       );
 
       const now = Math.floor(Date.now() / 1000);
-      const deadline = new BN(now + 3600); // 1 hour
+      const deadline = new BN(now + 60); // 60 days in accelerated time
 
       await program.methods
-        .createProposal("Test Suite Proposal", "Description", deadline)
+        .createProposal("Test Suite Proposal", "Description", deadline, false)
         .accounts({
             globalAccount: globalPDAAddress,
             proposalAccount: proposalPDAAddress,
@@ -216,20 +480,23 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
             globalAccount: globalPDAAddress,
             proposalAccount: proposalPDAAddress,
             voterRecord: voterRecordPDA,
-            stakeRecord: stakeRecordPDA,
             userTokenAccount: user1ATA,
             user: user1.publicKey,
         })
+        .remainingAccounts(positionAccounts(user1Positions))
         .signers([user1])
         .rpc();
 
       const proposal = await program.account.proposalAccount.fetch(proposalPDAAddress);
+      const record = await program.account.voterRecord.fetch(voterRecordPDA);
       
       // Expected:
       // Liquid: Sqrt(50) = 7
-      // Staked: Sqrt(100) * 2 = 20
-      // Total: 27
-      expect(proposal.yes.toNumber()).to.eq(27);
+      // Staked: Sqrt(120) = 10, times the amount-weighted ve multiplier of both
+      // positions, which decays towards 1x as the locks run down
+      // Total: between 17 and 41
+      expect(proposal.yes.toNumber()).to.eq(record.votingPower.toNumber());
+      expect(proposal.yes.toNumber()).to.be.within(17, 41);
   });
 
   it("User 1 Cannot Vote YES again (AlreadyVoted)", async () => {
@@ -244,10 +511,10 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
                 globalAccount: globalPDAAddress,
                 proposalAccount: proposalPDAAddress,
                 voterRecord: voterRecordPDA,
-                stakeRecord: stakeRecordPDA,
                 userTokenAccount: user1ATA,
                 user: user1.publicKey,
             })
+            .remainingAccounts(positionAccounts(user1Positions))
             .signers([user1])
             .rpc();
         expect.fail("Should have failed");
@@ -267,16 +534,180 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
             globalAccount: globalPDAAddress,
             proposalAccount: proposalPDAAddress,
             voterRecord: voterRecordPDA,
-            stakeRecord: stakeRecordPDA,
             userTokenAccount: user1ATA,
             user: user1.publicKey,
         })
+        .remainingAccounts(positionAccounts(user1Positions))
         .signers([user1])
         .rpc();
 
       const proposal = await program.account.proposalAccount.fetch(proposalPDAAddress);
+      const record = await program.account.voterRecord.fetch(voterRecordPDA);
       expect(proposal.yes.toNumber()).to.eq(0);
-      expect(proposal.no.toNumber()).to.eq(27);
+      expect(proposal.no.toNumber()).to.eq(record.votingPower.toNumber());
+  });
+
+  let batchProposalPDAs: anchor.web3.PublicKey[] = [];
+
+  it("User 1 Batch Votes on Two Proposals", async () => {
+      for (let i = 0; i < 2; i++) {
+          const globalAccount = await program.account.globalAccount.fetch(globalPDAAddress);
+          const buffer = Buffer.alloc(8);
+          buffer.writeBigUInt64LE(BigInt(globalAccount.proposalCount.toNumber() + 1));
+
+          const [pPDA] = await anchor.web3.PublicKey.findProgramAddress(
+              [Buffer.from("proposal"), buffer],
+              program.programId
+          );
+
+          await program.methods
+            .createProposal(`Batch Proposal ${i}`, "Description", new BN(Math.floor(Date.now() / 1000) + 60), false)
+            .accounts({
+                globalAccount: globalPDAAddress,
+                proposalAccount: pPDA,
+                author: owner.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .rpc();
+          batchProposalPDAs.push(pPDA);
+      }
+
+      const remainingAccounts = batchProposalPDAs.flatMap((pPDA) => {
+          const [vRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+              [Buffer.from("voter"), pPDA.toBuffer(), user1.publicKey.toBuffer()],
+              program.programId
+          );
+          return [
+              { pubkey: pPDA, isWritable: true, isSigner: false },
+              { pubkey: vRecord, isWritable: true, isSigner: false },
+          ];
+      });
+
+      await program.methods
+        .voteBatch(batchProposalPDAs.map((proposal) => ({ proposal, voteYes: true })), false)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            userTokenAccount: user1ATA,
+            user: user1.publicKey,
+        })
+        .remainingAccounts([...remainingAccounts, ...positionAccounts(user1Positions)])
+        .signers([user1])
+        .rpc();
+
+      // Power is computed once for the whole batch
+      const first = await program.account.proposalAccount.fetch(batchProposalPDAs[0]);
+      const second = await program.account.proposalAccount.fetch(batchProposalPDAs[1]);
+      expect(first.yes.toNumber()).to.be.within(17, 41);
+      expect(second.yes.toNumber()).to.eq(first.yes.toNumber());
+  });
+
+  it("Best-Effort Batch Skips Failing Entries", async () => {
+      const remainingAccounts = batchProposalPDAs.flatMap((pPDA) => {
+          const [vRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+              [Buffer.from("voter"), pPDA.toBuffer(), user1.publicKey.toBuffer()],
+              program.programId
+          );
+          return [
+              { pubkey: pPDA, isWritable: true, isSigner: false },
+              { pubkey: vRecord, isWritable: true, isSigner: false },
+          ];
+      });
+
+      // Entry 0 repeats YES (AlreadyVoted), entry 1 switches to NO
+      await program.methods
+        .voteBatch([
+            { proposal: batchProposalPDAs[0], voteYes: true },
+            { proposal: batchProposalPDAs[1], voteYes: false },
+        ], true)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            userTokenAccount: user1ATA,
+            user: user1.publicKey,
+        })
+        .remainingAccounts([...remainingAccounts, ...positionAccounts(user1Positions)])
+        .signers([user1])
+        .rpc();
+
+      const [secondRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("voter"), batchProposalPDAs[1].toBuffer(), user1.publicKey.toBuffer()],
+          program.programId
+      );
+
+      const first = await program.account.proposalAccount.fetch(batchProposalPDAs[0]);
+      expect(first.no.toNumber()).to.eq(0);
+
+      const second = await program.account.proposalAccount.fetch(batchProposalPDAs[1]);
+      const secondRecord = await program.account.voterRecord.fetch(secondRecordPDA);
+      expect(second.yes.toNumber()).to.eq(0);
+      expect(second.no.toNumber()).to.eq(secondRecord.votingPower.toNumber());
+  });
+
+  it("Relayer Submits a Signed Vote for a Voter Without SOL", async () => {
+      const gaslessVoter = anchor.web3.Keypair.generate();
+      const gaslessATA = (await getOrCreateAssociatedTokenAccount(provider.connection, (owner as any).payer, mint, gaslessVoter.publicKey)).address;
+      await mintTo(provider.connection, (owner as any).payer, mint, gaslessATA, owner.publicKey, 16);
+
+      const proposal = batchProposalPDAs[0];
+      const nonce = new BN(0);
+      const expiry = new BN(Math.floor(Date.now() / 1000) + 600);
+
+      // program_id | proposal | vote_yes | nonce (LE) | expiry (LE)
+      const message = Buffer.concat([
+          program.programId.toBuffer(),
+          proposal.toBuffer(),
+          Buffer.from([1]),
+          nonce.toArrayLike(Buffer, "le", 8),
+          expiry.toArrayLike(Buffer, "le", 8),
+      ]);
+
+      const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: gaslessVoter.secretKey,
+          message,
+      });
+
+      const [voterRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("voter"), proposal.toBuffer(), gaslessVoter.publicKey.toBuffer()],
+          program.programId
+      );
+
+      const before = await program.account.proposalAccount.fetch(proposal);
+
+      await program.methods
+        .voteWithSignature(true, nonce, expiry)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            proposalAccount: proposal,
+            voterRecord: voterRecordPDA,
+            voterTokenAccount: gaslessATA,
+            voter: gaslessVoter.publicKey,
+            instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+            relayer: owner.publicKey,
+        })
+        .preInstructions([ed25519Ix])
+        .rpc();
+
+      const after = await program.account.proposalAccount.fetch(proposal);
+      expect(after.yes.toNumber()).to.eq(before.yes.toNumber() + 4); // sqrt(16)
+
+      // Replaying the same nonce must fail
+      try {
+          await program.methods
+            .voteWithSignature(true, nonce, expiry)
+            .accounts({
+                globalAccount: globalPDAAddress,
+                proposalAccount: proposal,
+                voterRecord: voterRecordPDA,
+                voterTokenAccount: gaslessATA,
+                voter: gaslessVoter.publicKey,
+                instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+                relayer: owner.publicKey,
+            })
+            .preInstructions([ed25519Ix])
+            .rpc();
+          expect.fail("Should have failed with InvalidNonce");
+      } catch(e) {
+          expect(e.message).to.include("InvalidNonce");
+      }
   });
 
   /*
@@ -371,19 +802,96 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
           program.programId
       );
 
-      await program.methods.delegateVote()
+      // Unregistered keys cannot receive delegations
+      const stranger = anchor.web3.Keypair.generate();
+      const [strangerProfilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("delegate_profile"), stranger.publicKey.toBuffer()],
+          program.programId
+      );
+      try {
+          await program.methods.delegateVote(openTerms)
+          .accounts({
+              delegationRecord: delegationRecordPDA,
+              targetDelegate: stranger.publicKey,
+              targetDelegateProfile: strangerProfilePDA,
+              user: user1.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId
+          })
+          .signers([user1])
+          .rpc();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("AccountNotInitialized");
+      }
+
+      // Every position User 1 opened is settled, so all of them must be passed
+      try {
+          await program.methods.delegateVote(openTerms)
+          .accounts({
+              delegationRecord: delegationRecordPDA,
+              targetDelegate: delegate.publicKey,
+              targetDelegateProfile: delegateProfilePDA,
+              user: user1.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId
+          })
+          .signers([user1])
+          .rpc();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("MissingStakePosition");
+      }
+
+      await program.methods.delegateVote(openTerms)
       .accounts({
           delegationRecord: delegationRecordPDA,
           targetDelegate: delegate.publicKey,
+          targetDelegateProfile: delegateProfilePDA,
           user: user1.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId
       })
+      .remainingAccounts(positionAccounts(user1Positions))
+      .signers([user1])
+      .rpc();
+
+      let profile = await program.account.delegateProfile.fetch(delegateProfilePDA);
+      expect(profile.delegatorCount.toNumber()).to.equal(1);
+      expect(profile.delegatedStake.toNumber()).to.be.greaterThan(0);
+
+      const resync = (positions: anchor.web3.PublicKey[]) => program.methods.resyncDelegation()
+      .accounts({
+          delegationRecord: delegationRecordPDA,
+          stakerProfile: stakerProfilePDA,
+          delegator: user1.publicKey,
+          user: user1.publicKey,
+      })
+      .remainingAccounts([
+          { pubkey: delegateProfilePDA, isWritable: true, isSigner: false },
+          ...positionAccounts(positions),
+      ])
       .signers([user1])
       .rpc();
 
+      // A re-sync must see every position, so none can be left out to shrink the stake
+      try {
+          await resync(user1Positions.slice(0, 1));
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("MissingStakePosition");
+      }
+
+      // Re-sync credits User 1's staked positions
+      await resync(user1Positions);
+
+      profile = await program.account.delegateProfile.fetch(delegateProfilePDA);
+      expect(profile.delegatorCount.toNumber()).to.equal(1);
+      expect(profile.delegatedStake.toNumber()).to.be.greaterThan(0);
+
       const record = await program.account.delegationRecord.fetch(delegationRecordPDA);
+      expect(record.delegatedStake.toNumber()).to.equal(profile.delegatedStake.toNumber());
       expect(record.delegator.toString()).to.equal(user1.publicKey.toString());
-      expect(record.delegateTarget.toString()).to.equal(delegate.publicKey.toString());
+      expect(record.shares.length).to.equal(1);
+      expect(record.shares[0].delegate.toString()).to.equal(delegate.publicKey.toString());
+      expect(record.shares[0].bps).to.equal(10000);
   });
 
   it("Creates Proposal 2 for Delegation Tests", async () => {
@@ -399,10 +907,10 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
       );
 
       const now = Math.floor(Date.now() / 1000);
-      const deadline = new BN(now + 3600); // 1 hour
+      const deadline = new BN(now + 60); // 60 days in accelerated time
 
       await program.methods
-        .createProposal("Delegation Test Proposal", "Description", deadline)
+        .createProposal("Delegation Test Proposal", "Description", deadline, false)
         .accounts({
             globalAccount: globalPDAAddress,
             proposalAccount: proposal2PDA,
@@ -412,36 +920,6 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
         .rpc();
   });
 
-  it("User 1 Cannot Vote Directly (DelegatorsCannotVote)", async () => {
-      const [voterRecordPDA] = await anchor.web3.PublicKey.findProgramAddress(
-          [Buffer.from("voter"), proposal2PDA.toBuffer(), user1.publicKey.toBuffer()],
-          program.programId
-      );
-      
-      const [delegationRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-          [Buffer.from("delegation_record"), user1.publicKey.toBuffer()],
-          program.programId
-      );
-
-      try {
-        await program.methods.vote(true)
-            .accounts({
-                globalAccount: globalPDAAddress,
-                proposalAccount: proposal2PDA,
-                voterRecord: voterRecordPDA,
-                stakeRecord: stakeRecordPDA,
-                userTokenAccount: user1ATA,
-                user: user1.publicKey,
-                delegationRecord: delegationRecordPDA // Now Checked
-            })
-            .signers([user1])
-            .rpc();
-        expect.fail("Should have failed");
-      } catch(e) {
-        expect(e.message).to.include("DelegatorsCannotVote");
-      }
-  });
-
   it("User 2 Votes as Proxy for User 1", async () => {
       const [voterRecordPDA] = await anchor.web3.PublicKey.findProgramAddress(
           [Buffer.from("voter"), proposal2PDA.toBuffer(), user1.publicKey.toBuffer()],
@@ -458,28 +936,43 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
           program.programId
       );
 
+      const [participationPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("delegate_participation"), proposal2PDA.toBuffer(), user2.publicKey.toBuffer()],
+          program.programId
+      );
+
       await program.methods.voteAsProxy(true)
         .accounts({
             globalAccount: globalPDAAddress,
             proposalAccount: proposal2PDA,
             delegateProfile: delegateProfilePDA,
+            participation: participationPDA,
             delegationRecord: delegationRecordPDA,
             voterRecord: voterRecordPDA,
             delegatorTokenAccount: user1ATA,
-            delegatorStakeRecord: stakeRecordPDA,
             delegatorUser: user1.publicKey,
             proxyAuthority: user2.publicKey,
             systemProgram: anchor.web3.SystemProgram.programId
         })
+        .remainingAccounts(positionAccounts(user1Positions))
         .signers([user2])
         .rpc();
 
       const proposal = await program.account.proposalAccount.fetch(proposal2PDA);
-      // User 1 Power: 150 Tokens (100 staked for 30d 2x) -> 7 + 20 = 27
-      expect(proposal.yes.toNumber()).to.eq(27);
-      
       const vRecord = await program.account.voterRecord.fetch(voterRecordPDA);
+      // User 1 Power: 7 liquid + 120 staked whose lock bonus decays towards 1x
+      expect(vRecord.proxyVotes.length).to.equal(1);
+      expect(vRecord.proxyVotes[0].delegate.toString()).to.equal(user2.publicKey.toString());
+      expect(proposal.yes.toNumber()).to.eq(vRecord.proxyVotes[0].votingPower.toNumber());
+      expect(proposal.yes.toNumber()).to.be.within(17, 41);
+
       expect(vRecord.votedByProxy).to.be.true;
+      expect(vRecord.voted).to.be.false;
+
+      // The delegate's participation is recorded
+      const profile = await program.account.delegateProfile.fetch(delegateProfilePDA);
+      expect(profile.proposalsVoted.toNumber()).to.equal(1);
+      expect(profile.lastActiveTime.toNumber()).to.be.greaterThan(0);
   });
 
   it("User 1 Cannot Withdraw Proxy Vote (ProxyVoteLocked)", async () => {
@@ -504,20 +997,192 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
       }
   });
 
-  it("User 1 Revokes Delegation", async () => {
-    const [delegationRecordPDA] = await anchor.web3.PublicKey.findProgramAddress(
-        [Buffer.from("delegation_record"), user1.publicKey.toBuffer()],
-        program.programId
-    );
+  it("User 1 Overrides a Proxy Vote Without Revoking", async () => {
+      const globalAccount = await program.account.globalAccount.fetch(globalPDAAddress);
+      const buffer = Buffer.alloc(8);
+      buffer.writeBigUInt64LE(BigInt(globalAccount.proposalCount.toNumber() + 1));
+      const [overridePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("proposal"), buffer],
+          program.programId
+      );
 
-    await program.methods.revokeDelegation()
+      await program.methods
+        .createProposal("Override Test Proposal", "Description", new BN(Math.floor(Date.now() / 1000) + 60), false)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            proposalAccount: overridePDA,
+            author: owner.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const [voterRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("voter"), overridePDA.toBuffer(), user1.publicKey.toBuffer()],
+          program.programId
+      );
+      const proxyVote = () => program.methods.voteAsProxy(true)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            proposalAccount: overridePDA,
+            delegateProfile: anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("delegate_profile"), user2.publicKey.toBuffer()],
+                program.programId
+            )[0],
+            participation: anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("delegate_participation"), overridePDA.toBuffer(), user2.publicKey.toBuffer()],
+                program.programId
+            )[0],
+            delegationRecord: anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("delegation_record"), user1.publicKey.toBuffer()],
+                program.programId
+            )[0],
+            voterRecord: voterRecordPDA,
+            delegatorTokenAccount: user1ATA,
+            delegatorUser: user1.publicKey,
+            proxyAuthority: user2.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId
+        })
+        .remainingAccounts(positionAccounts(user1Positions))
+        .signers([user2])
+        .rpc();
+
+      await proxyVote();
+
+      // User 1 still delegates, but votes NO directly on this proposal
+      await program.methods.vote(false)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            proposalAccount: overridePDA,
+            voterRecord: voterRecordPDA,
+            userTokenAccount: user1ATA,
+            user: user1.publicKey,
+        })
+        .remainingAccounts(positionAccounts(user1Positions))
+        .signers([user1])
+        .rpc();
+
+      const proposal = await program.account.proposalAccount.fetch(overridePDA);
+      const vRecord = await program.account.voterRecord.fetch(voterRecordPDA);
+      expect(proposal.yes.toNumber()).to.equal(0);
+      expect(proposal.no.toNumber()).to.equal(vRecord.votingPower.toNumber());
+      expect(vRecord.votedByProxy).to.be.false;
+      expect(vRecord.proxyVotes.length).to.equal(0);
+
+      // The proxy can no longer vote User 1's power on this proposal
+      try {
+          await proxyVote();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("DirectVoteExists");
+      }
+
+      // Not even after User 1 withdraws the direct vote
+      await program.methods.withdrawVote()
+        .accounts({
+            globalAccount: globalPDAAddress,
+            proposalAccount: overridePDA,
+            voterRecord: voterRecordPDA,
+            user: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+      expect((await program.account.voterRecord.fetch(voterRecordPDA)).overridden).to.be.true;
+      try {
+          await proxyVote();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("DirectVoteExists");
+      }
+  });
+
+  it("User 2 Votes as Proxy for All Delegators in One Call", async () => {
+      const globalAccount = await program.account.globalAccount.fetch(globalPDAAddress);
+      const buffer = Buffer.alloc(8);
+      buffer.writeBigUInt64LE(BigInt(globalAccount.proposalCount.toNumber() + 1));
+      const [batchProposalPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("proposal"), buffer],
+          program.programId
+      );
+
+      await program.methods
+        .createProposal("Proxy Batch Proposal", "Description", new BN(Math.floor(Date.now() / 1000) + 60), false)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            proposalAccount: batchProposalPDA,
+            author: owner.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const [voterRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("voter"), batchProposalPDA.toBuffer(), user1.publicKey.toBuffer()],
+          program.programId
+      );
+      const [delegationRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("delegation_record"), user1.publicKey.toBuffer()],
+          program.programId
+      );
+
+      // (delegation_record, token_account, stake positions.., voter_record) per delegator
+      await program.methods.voteAsProxyBatch(true, Buffer.from([user1Positions.length]))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            proposalAccount: batchProposalPDA,
+            delegateProfile: anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("delegate_profile"), user2.publicKey.toBuffer()],
+                program.programId
+            )[0],
+            participation: anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("delegate_participation"), batchProposalPDA.toBuffer(), user2.publicKey.toBuffer()],
+                program.programId
+            )[0],
+            proxyAuthority: user2.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId
+        })
+        .remainingAccounts([
+            { pubkey: delegationRecordPDA, isWritable: false, isSigner: false },
+            { pubkey: user1ATA, isWritable: false, isSigner: false },
+            ...positionAccounts(user1Positions),
+            { pubkey: voterRecordPDA, isWritable: true, isSigner: false },
+        ])
+        .signers([user2])
+        .rpc();
+
+      const proposal = await program.account.proposalAccount.fetch(batchProposalPDA);
+      const vRecord = await program.account.voterRecord.fetch(voterRecordPDA);
+      expect(vRecord.votedByProxy).to.be.true;
+      expect(vRecord.proxyVotes.length).to.equal(1);
+      expect(proposal.yes.toNumber()).to.equal(vRecord.proxyVotes[0].votingPower.toNumber());
+      expect(proposal.yes.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("User 1 Revokes Delegation", async () => {
+    const [delegationRecordPDA] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("delegation_record"), user1.publicKey.toBuffer()],
+        program.programId
+    );
+    const [delegateProfilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("delegate_profile"), user2.publicKey.toBuffer()],
+        program.programId
+    );
+
+    await program.methods.revokeDelegation()
       .accounts({
           delegationRecord: delegationRecordPDA,
+          ...noRewardVault,
           user: user1.publicKey,
       })
+      .remainingAccounts([
+          ...commissionAccounts(delegateProfilePDA, user2ATA),
+          ...positionAccounts(user1Positions),
+      ])
       .signers([user1])
       .rpc();
 
+    const profile = await program.account.delegateProfile.fetch(delegateProfilePDA);
+    expect(profile.delegatorCount.toNumber()).to.equal(0);
+    expect(profile.delegatedStake.toNumber()).to.equal(0);
+
     // Verify account is closed
     try {
         await program.account.delegationRecord.fetch(delegationRecordPDA);
@@ -550,85 +1215,477 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
       }
   });
 
-  it("Admin Removes Delegate", async () => {
-    const delegate = user2;
-    const [delegateProfilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from("delegate_profile"), delegate.publicKey.toBuffer()],
-        program.programId
-    );
+  it("Proxy Votes Respect Delegation Scope and Expiry", async () => {
+      const globalAccount = await program.account.globalAccount.fetch(globalPDAAddress);
+      const buffer = Buffer.alloc(8);
+      buffer.writeBigUInt64LE(BigInt(globalAccount.proposalCount.toNumber() + 1));
+      const [scopedProposalPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("proposal"), buffer],
+          program.programId
+      );
+      await program.methods
+        .createProposal("Scope Test Proposal", "Description", new BN(Math.floor(Date.now() / 1000) + 60), false)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            proposalAccount: scopedProposalPDA,
+            author: owner.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
 
-    await program.methods.removeDelegate()
-      .accounts({
-          globalAccount: globalPDAAddress,
-          delegateProfile: delegateProfilePDA,
-          targetUser: delegate.publicKey,
-          admin: owner.publicKey,
-      })
-      .rpc();
+      const [delegationRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("delegation_record"), user1.publicKey.toBuffer()],
+          program.programId
+      );
+      const [delegateProfilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("delegate_profile"), user2.publicKey.toBuffer()],
+          program.programId
+      );
+      const delegate = (terms) => program.methods.delegateVote(terms)
+        .accounts({
+            delegationRecord: delegationRecordPDA,
+            targetDelegate: user2.publicKey,
+            targetDelegateProfile: delegateProfilePDA,
+            user: user1.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId
+        })
+        .remainingAccounts(positionAccounts(user1Positions))
+        .signers([user1])
+        .rpc();
+      const revoke = () => program.methods.revokeDelegation()
+        .accounts({
+            delegationRecord: delegationRecordPDA,
+            ...noRewardVault,
+            user: user1.publicKey,
+        })
+        .remainingAccounts([
+            ...commissionAccounts(delegateProfilePDA, user2ATA),
+            ...positionAccounts(user1Positions),
+        ])
+        .signers([user1])
+        .rpc();
+      const proxyVote = () => program.methods.voteAsProxy(true)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            proposalAccount: scopedProposalPDA,
+            delegateProfile: delegateProfilePDA,
+            participation: anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("delegate_participation"), scopedProposalPDA.toBuffer(), user2.publicKey.toBuffer()],
+                program.programId
+            )[0],
+            delegationRecord: delegationRecordPDA,
+            voterRecord: anchor.web3.PublicKey.findProgramAddressSync(
+                [Buffer.from("voter"), scopedProposalPDA.toBuffer(), user1.publicKey.toBuffer()],
+                program.programId
+            )[0],
+            delegatorTokenAccount: user1ATA,
+            delegatorUser: user1.publicKey,
+            proxyAuthority: user2.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId
+        })
+        .remainingAccounts(positionAccounts(user1Positions))
+        .signers([user2])
+        .rpc();
 
-    try {
-        await program.account.delegateProfile.fetch(delegateProfilePDA);
-        expect.fail("Delegate Profile should be closed");
-    } catch(e) {
-        expect(e.message).to.include("Account does not exist");
-    }
+      // Treasury-only delegation cannot vote on a standard proposal
+      await delegate({ expiresAt: new BN(0), scope: 0b10 });
+      try {
+          await proxyVote();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("ProposalOutOfScope");
+      }
+      await revoke();
+
+      // Expired delegation cannot vote
+      await delegate({ expiresAt: new BN(Math.floor(Date.now() / 1000) + 3), scope: 0xffffffff });
+      await new Promise(resolve => setTimeout(resolve, 5000));
+      try {
+          await proxyVote();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("DelegationExpired");
+      }
+      await revoke();
   });
 
-  // =========================================================================
-  // TREASURY PROPOSALS
-  // =========================================================================
+  it("User 1 Splits Delegation Across Two Delegates", async () => {
+      const secondDelegate = anchor.web3.Keypair.generate();
+      const [delegationRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("delegation_record"), user1.publicKey.toBuffer()],
+          program.programId
+      );
+      const delegateProfiles = [user2, secondDelegate].map((d) =>
+          anchor.web3.PublicKey.findProgramAddressSync(
+              [Buffer.from("delegate_profile"), d.publicKey.toBuffer()],
+              program.programId
+          )[0]
+      );
+      const profileAccounts = delegateProfiles.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }));
 
-  let treasuryProposalPDA: anchor.web3.PublicKey;
-  let treasuryProposalId: number;
-  let proposalEscrowPDA: anchor.web3.PublicKey;
-  const destinationUser = anchor.web3.Keypair.generate();
-  let destinationATA: anchor.web3.PublicKey;
+      // Split targets must be registered delegates
+      try {
+          await program.methods.delegateSplit([
+              { delegate: user2.publicKey, bps: 6000 },
+              { delegate: secondDelegate.publicKey, bps: 4000 },
+          ], openTerms)
+          .accounts({
+              delegationRecord: delegationRecordPDA,
+              user: user1.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId
+          })
+          .remainingAccounts(profileAccounts)
+          .signers([user1])
+          .rpc();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("InvalidDelegate");
+      }
 
-  it("Setup: Fund destination user and create ATA", async () => {
-    // Fund destination user
-    const tx = new anchor.web3.Transaction().add(
-      anchor.web3.SystemProgram.transfer({
-        fromPubkey: provider.wallet.publicKey,
-        toPubkey: destinationUser.publicKey,
-        lamports: 0.1 * anchor.web3.LAMPORTS_PER_SOL,
+      await program.methods.registerDelegate()
+        .accounts({
+            globalAccount: globalPDAAddress,
+            delegateProfile: delegateProfiles[1],
+            targetUser: secondDelegate.publicKey,
+            admin: owner.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      // Shares must sum to 100%
+      try {
+          await program.methods.delegateSplit([
+              { delegate: user2.publicKey, bps: 6000 },
+              { delegate: secondDelegate.publicKey, bps: 3000 },
+          ], openTerms)
+          .accounts({
+              delegationRecord: delegationRecordPDA,
+              user: user1.publicKey,
+              systemProgram: anchor.web3.SystemProgram.programId
+          })
+          .signers([user1])
+          .rpc();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("InvalidDelegationShares");
+      }
+
+      await program.methods.delegateSplit([
+          { delegate: user2.publicKey, bps: 6000 },
+          { delegate: secondDelegate.publicKey, bps: 4000 },
+      ], openTerms)
+      .accounts({
+          delegationRecord: delegationRecordPDA,
+          user: user1.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId
       })
-    );
-    await provider.sendAndConfirm(tx);
+      .remainingAccounts([...profileAccounts, ...positionAccounts(user1Positions)])
+      .signers([user1])
+      .rpc();
 
-    // Create ATA for destination
-    destinationATA = (await getOrCreateAssociatedTokenAccount(
-      provider.connection, 
-      (owner as any).payer, 
-      mint, 
-      destinationUser.publicKey
-    )).address;
+      const record = await program.account.delegationRecord.fetch(delegationRecordPDA);
+      expect(record.shares.map((s: any) => s.bps)).to.deep.equal([6000, 4000]);
+      expect(record.shares[1].delegate.toString()).to.equal(secondDelegate.publicKey.toString());
+
+      // Each delegate is credited with their share of the stake
+      const secondProfile = await program.account.delegateProfile.fetch(delegateProfiles[1]);
+      expect(secondProfile.delegatorCount.toNumber()).to.equal(1);
+      expect(secondProfile.delegatedStake.toNumber()).to.equal(
+          Math.floor(record.delegatedStake.toNumber() * 4000 / 10000)
+      );
+
+      const secondDelegateATA = (await getOrCreateAssociatedTokenAccount(
+          provider.connection, (owner as any).payer, mint, secondDelegate.publicKey
+      )).address;
+      await program.methods.revokeDelegation()
+        .accounts({
+            delegationRecord: delegationRecordPDA,
+            ...noRewardVault,
+            user: user1.publicKey,
+        })
+        .remainingAccounts([
+            ...commissionAccounts(delegateProfiles[0], user2ATA),
+            ...commissionAccounts(delegateProfiles[1], secondDelegateATA),
+            ...positionAccounts(user1Positions),
+        ])
+        .signers([user1])
+        .rpc();
   });
 
-  it("Creates Treasury Proposal with Token Escrow", async () => {
-    const globalAccount = await program.account.globalAccount.fetch(globalPDAAddress);
-    treasuryProposalId = globalAccount.proposalCount.toNumber() + 1;
+  const candidate = anchor.web3.Keypair.generate();
 
-    const buffer = Buffer.alloc(8);
-    buffer.writeBigUInt64LE(BigInt(treasuryProposalId));
+  it("Candidate Self-Nominates, Is Approved and Updates Metadata", async () => {
+      await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+          anchor.web3.SystemProgram.transfer({
+              fromPubkey: provider.wallet.publicKey,
+              toPubkey: candidate.publicKey,
+              lamports: 0.1 * anchor.web3.LAMPORTS_PER_SOL,
+          })
+      ));
 
-    [treasuryProposalPDA] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from("proposal"), buffer],
-      program.programId
-    );
+      const [candidateProfilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("delegate_profile"), candidate.publicKey.toBuffer()],
+          program.programId
+      );
+      const [candidateDelegationPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("delegation_record"), candidate.publicKey.toBuffer()],
+          program.programId
+      );
 
-    [proposalEscrowPDA] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from("proposal_escrow"), buffer],
-      program.programId
-    );
+      await program.methods.nominateDelegate({
+          displayName: "Ada",
+          platformUri: "https://example.com/platform.md",
+          platformHash: Array(32).fill(7),
+          focusTags: ["treasury", "security"],
+      })
+      .accounts({
+          globalAccount: globalPDAAddress,
+          delegateProfile: candidateProfilePDA,
+          userDelegationRecord: candidateDelegationPDA,
+          user: candidate.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([candidate])
+      .rpc();
 
-    // User1 creates treasury proposal with 50 tokens
-    const now = Math.floor(Date.now() / 1000);
-    const deadline = new BN(now + 5); // 5 seconds for testing
-    const transferAmount = new BN(50);
-    const timelockSeconds = new BN(2); // 2 seconds timelock
+      let profile = await program.account.delegateProfile.fetch(candidateProfilePDA);
+      expect(profile.isActive).to.be.false;
+      expect(profile.displayName).to.equal("Ada");
+      expect(profile.focusTags).to.deep.equal(["treasury", "security"]);
 
-    // Check user1 balance before
-    const balBefore = await provider.connection.getTokenAccountBalance(user1ATA);
+      await program.methods.reinstateDelegate("Nomination approved")
+        .accounts({
+            globalAccount: globalPDAAddress,
+            delegateProfile: candidateProfilePDA,
+            targetUser: candidate.publicKey,
+            admin: owner.publicKey,
+        })
+        .rpc();
+
+      await program.methods.updateDelegateMetadata({
+          displayName: "Ada L.",
+          platformUri: "https://example.com/platform-v2.md",
+          platformHash: Array(32).fill(9),
+          focusTags: ["grants"],
+      })
+      .accounts({
+          delegateProfile: candidateProfilePDA,
+          user: candidate.publicKey,
+      })
+      .signers([candidate])
+      .rpc();
+
+      profile = await program.account.delegateProfile.fetch(candidateProfilePDA);
+      expect(profile.isActive).to.be.true;
+      expect(profile.statusReason).to.equal("Nomination approved");
+      expect(profile.displayName).to.equal("Ada L.");
+      expect(profile.focusTags).to.deep.equal(["grants"]);
+  });
+
+  it("Admin Suspends and Removes a Delegate, Voiding Their Delegations", async () => {
+    const delegate = user2;
+    const [delegateProfilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("delegate_profile"), delegate.publicKey.toBuffer()],
+        program.programId
+    );
+    const [delegationRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("delegation_record"), user1.publicKey.toBuffer()],
+        program.programId
+    );
+
+    // User 1 delegates to User 2 again
+    await program.methods.delegateVote(openTerms)
+      .accounts({
+          delegationRecord: delegationRecordPDA,
+          targetDelegate: delegate.publicKey,
+          targetDelegateProfile: delegateProfilePDA,
+          user: user1.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId
+      })
+      .remainingAccounts(positionAccounts(user1Positions))
+      .signers([user1])
+      .rpc();
+
+    // User 2 casts a proxy vote that is still in flight when they are removed
+    const globalAccount = await program.account.globalAccount.fetch(globalPDAAddress);
+    const buffer = Buffer.alloc(8);
+    buffer.writeBigUInt64LE(BigInt(globalAccount.proposalCount.toNumber() + 1));
+    const [removalProposalPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("proposal"), buffer],
+        program.programId
+    );
+    const [voterRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("voter"), removalProposalPDA.toBuffer(), user1.publicKey.toBuffer()],
+        program.programId
+    );
+    await program.methods
+      .createProposal("Removal Test Proposal", "Description", new BN(Math.floor(Date.now() / 1000) + 60), false)
+      .accounts({
+          globalAccount: globalPDAAddress,
+          proposalAccount: removalProposalPDA,
+          author: owner.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods.voteAsProxy(true)
+      .accounts({
+          globalAccount: globalPDAAddress,
+          proposalAccount: removalProposalPDA,
+          delegateProfile: delegateProfilePDA,
+          participation: anchor.web3.PublicKey.findProgramAddressSync(
+              [Buffer.from("delegate_participation"), removalProposalPDA.toBuffer(), delegate.publicKey.toBuffer()],
+              program.programId
+          )[0],
+          delegationRecord: delegationRecordPDA,
+          voterRecord: voterRecordPDA,
+          delegatorTokenAccount: user1ATA,
+          delegatorUser: user1.publicKey,
+          proxyAuthority: delegate.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId
+      })
+      .remainingAccounts(positionAccounts(user1Positions))
+      .signers([delegate])
+      .rpc();
+
+    const statusAccounts = {
+        globalAccount: globalPDAAddress,
+        delegateProfile: delegateProfilePDA,
+        targetUser: delegate.publicKey,
+        admin: owner.publicKey,
+    };
+    const clearAccounts = {
+        globalAccount: globalPDAAddress,
+        proposalAccount: removalProposalPDA,
+        delegateProfile: delegateProfilePDA,
+        voterRecord: voterRecordPDA,
+        delegate: delegate.publicKey,
+        delegatorUser: user1.publicKey,
+    };
+
+    // Removal requires a suspension first, and an active delegate's votes stand
+    try {
+        await program.methods.removeDelegate().accounts(statusAccounts).rpc();
+        expect.fail("Should have failed");
+    } catch(e) {
+        expect(e.message).to.include("DelegateNotSuspended");
+    }
+    try {
+        await program.methods.clearProxyVotes().accounts(clearAccounts).rpc();
+        expect.fail("Should have failed");
+    } catch(e) {
+        expect(e.message).to.include("DelegateNotSuspended");
+    }
+
+    // Only the admin or a council member can suspend
+    try {
+        await program.methods.suspendDelegate("Inactive")
+          .accounts({ ...statusAccounts, admin: user1.publicKey })
+          .signers([user1])
+          .rpc();
+        expect.fail("Should have failed");
+    } catch(e) {
+        expect(e.message).to.include("Unauthorized");
+    }
+
+    // Suspend without voiding; the delegation is left for removal to void
+    await program.methods.suspendDelegate("Conflict of interest")
+      .accounts(statusAccounts)
+      .rpc();
+
+    const suspended = await program.account.delegateProfile.fetch(delegateProfilePDA);
+    expect(suspended.isActive).to.be.false;
+    expect(suspended.statusReason).to.equal("Conflict of interest");
+    expect(suspended.delegatorCount.toNumber()).to.equal(1);
+
+    // The profile cannot be closed while a delegation record still names it
+    try {
+        await program.methods.removeDelegate().accounts(statusAccounts).rpc();
+        expect.fail("Should have failed");
+    } catch(e) {
+        expect(e.message).to.include("DelegatorsRemain");
+    }
+
+    await program.methods.removeDelegate()
+      .accounts(statusAccounts)
+      .remainingAccounts([{ pubkey: delegationRecordPDA, isWritable: true, isSigner: false }])
+      .rpc();
+
+    // The removed delegate's in-flight proxy vote is struck from the open proposal
+    await program.methods.clearProxyVotes().accounts(clearAccounts).rpc();
+
+    const proposal = await program.account.proposalAccount.fetch(removalProposalPDA);
+    expect(proposal.yes.toNumber()).to.equal(0);
+    const voterRecord = await program.account.voterRecord.fetch(voterRecordPDA);
+    expect(voterRecord.votedByProxy).to.be.false;
+    expect(voterRecord.proxyVotes.length).to.equal(0);
+
+    try {
+        await program.account.delegateProfile.fetch(delegateProfilePDA);
+        expect.fail("Delegate Profile should be closed");
+    } catch(e) {
+        expect(e.message).to.include("Account does not exist");
+    }
+
+    // The delegation is void without a revoke
+    const record = await program.account.delegationRecord.fetch(delegationRecordPDA);
+    expect(record.shares.length).to.equal(0);
+  });
+
+  // =========================================================================
+  // TREASURY PROPOSALS
+  // =========================================================================
+
+  let treasuryProposalPDA: anchor.web3.PublicKey;
+  let treasuryProposalId: number;
+  let proposalEscrowPDA: anchor.web3.PublicKey;
+  const destinationUser = anchor.web3.Keypair.generate();
+  let destinationATA: anchor.web3.PublicKey;
+
+  it("Setup: Fund destination user and create ATA", async () => {
+    // Fund destination user
+    const tx = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.transfer({
+        fromPubkey: provider.wallet.publicKey,
+        toPubkey: destinationUser.publicKey,
+        lamports: 0.1 * anchor.web3.LAMPORTS_PER_SOL,
+      })
+    );
+    await provider.sendAndConfirm(tx);
+
+    // Create ATA for destination
+    destinationATA = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, 
+      (owner as any).payer, 
+      mint, 
+      destinationUser.publicKey
+    )).address;
+  });
+
+  it("Creates Treasury Proposal with Token Escrow", async () => {
+    const globalAccount = await program.account.globalAccount.fetch(globalPDAAddress);
+    treasuryProposalId = globalAccount.proposalCount.toNumber() + 1;
+
+    const buffer = Buffer.alloc(8);
+    buffer.writeBigUInt64LE(BigInt(treasuryProposalId));
+
+    [treasuryProposalPDA] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("proposal"), buffer],
+      program.programId
+    );
+
+    [proposalEscrowPDA] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("proposal_escrow"), buffer],
+      program.programId
+    );
+
+    // User1 creates treasury proposal with 50 tokens
+    const now = Math.floor(Date.now() / 1000);
+    const deadline = new BN(now + 5); // 5 seconds for testing
+    const transferAmount = new BN(50);
+    const timelockSeconds = new BN(2); // 2 seconds timelock
+
+    // Check user1 balance before
+    const balBefore = await provider.connection.getTokenAccountBalance(user1ATA);
     const beforeAmount = parseInt(balBefore.value.amount);
 
     await program.methods
@@ -638,7 +1695,8 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
         deadline,
         transferAmount,
         destinationUser.publicKey,
-        timelockSeconds
+        timelockSeconds,
+        false
       )
       .accounts({
         globalAccount: globalPDAAddress,
@@ -745,6 +1803,39 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
     expect(parseInt(balAfter.value.amount)).to.eq(beforeAmount + 50);
   });
 
+  it("Records a Proposal the Delegate Missed", async () => {
+    const [candidateProfilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("delegate_profile"), candidate.publicKey.toBuffer()],
+        program.programId
+    );
+    const [participationPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("delegate_participation"), treasuryProposalPDA.toBuffer(), candidate.publicKey.toBuffer()],
+        program.programId
+    );
+    const accounts = {
+        proposalAccount: treasuryProposalPDA,
+        delegateProfile: candidateProfilePDA,
+        delegate: candidate.publicKey,
+        participation: participationPDA,
+        payer: owner.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await program.methods.recordMissedProposal().accounts(accounts).rpc();
+
+    const profile = await program.account.delegateProfile.fetch(candidateProfilePDA);
+    expect(profile.proposalsMissed.toNumber()).to.equal(1);
+    expect(profile.proposalsVoted.toNumber()).to.equal(0);
+
+    // A proposal is only counted once
+    try {
+        await program.methods.recordMissedProposal().accounts(accounts).rpc();
+        expect.fail("Should have failed");
+    } catch(e) {
+        expect(e.message).to.include("ParticipationRecorded");
+    }
+  });
+
   // Test successful execution flow
   let treasuryProposal2PDA: anchor.web3.PublicKey;
   let treasuryProposal2Id: number;
@@ -779,7 +1870,8 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
         deadline,
         transferAmount,
         destinationUser.publicKey,
-        timelockSeconds
+        timelockSeconds,
+        false
       )
       .accounts({
         globalAccount: globalPDAAddress,
@@ -802,21 +1894,13 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
       program.programId
     );
 
-    // User2 needs delegation record (empty = not delegating)
-    const [delegationRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("delegation_record"), user2.publicKey.toBuffer()],
-      program.programId
-    );
-
     await program.methods
       .vote(true)
       .accounts({
         globalAccount: globalPDAAddress,
         proposalAccount: treasuryProposal2PDA,
         voterRecord: voterRecordPDA,
-        stakeRecord: null,
         userTokenAccount: user2ATA,
-        delegationRecord: delegationRecordPDA,
         user: user2.publicKey,
       })
       .signers([user2])
@@ -882,9 +1966,9 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
             program.programId
         );
         
-        const deadline = new BN(Math.floor(Date.now() / 1000) + 3600);
+        const deadline = new BN(Math.floor(Date.now() / 1000) + 60);
 
-        await program.methods.createProposal(`Gamification Prop ${i}`, "Description", deadline)
+        await program.methods.createProposal(`Gamification Prop ${i}`, "Description", deadline, false)
             .accounts({
                 globalAccount: globalPDAAddress,
                 proposalAccount: pPDA,
@@ -899,23 +1983,15 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
             program.programId
         );
 
-        // Ensure user1 has no active delegation (revoked in previous test)
-        // Need delegation record PDA for the constraint check
-        const [delRecord] = anchor.web3.PublicKey.findProgramAddressSync(
-            [Buffer.from("delegation_record"), user1.publicKey.toBuffer()],
-            program.programId
-        );
-
         await program.methods.vote(true)
             .accounts({
                 globalAccount: globalPDAAddress,
                 proposalAccount: pPDA,
                 voterRecord: vRecord,
-                stakeRecord: stakeRecordPDA,
                 userTokenAccount: user1ATA,
                 user: user1.publicKey,
-                delegationRecord: delRecord
             })
+            .remainingAccounts(positionAccounts(user1Positions))
             .signers([user1])
             .rpc();
     }
@@ -987,4 +2063,1066 @@ describe("Pulsar DAO Comprehensive Test Suite", () => {
     }
   });
 
+  // =========================================================================
+  // PARTIAL UNSTAKING & UNBONDING
+  // =========================================================================
+
+  it("Anyone Drops an Expired Lock to 1x Reward Weight", async () => {
+      try {
+          await program.methods.expireLock(new BN(1))
+            .accounts({
+                globalAccount: globalPDAAddress,
+                rewardPool: rewardPoolPDA,
+                stakeRecord: user1Positions[1],
+                positionTokenAccount: null,
+                payer: user2.publicKey,
+            })
+            .signers([user2])
+            .rpc();
+          expect.fail("Should have failed with TokensLocked");
+      } catch(e) {
+          expect(e.message).to.include("TokensLocked");
+      }
+
+      // Position #0 has not changed since its 2x lock ran out
+      const position = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      const waitMs = (position.lockEndTime.toNumber() + 2) * 1000 - Date.now();
+      if (waitMs > 0) {
+          await new Promise(resolve => setTimeout(resolve, waitMs));
+      }
+      const globalBefore = await program.account.globalAccount.fetch(globalPDAAddress);
+      const poolBefore = await program.account.rewardPool.fetch(rewardPoolPDA);
+
+      await program.methods.expireLock(new BN(0))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: stakeRecordPDA,
+            positionTokenAccount: null,
+            payer: user2.publicKey,
+        })
+        .signers([user2])
+        .rpc();
+
+      const after = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      const dropped = position.rewardWeight.toNumber() - after.rewardWeight.toNumber();
+      expect(after.rewardWeight.toNumber()).to.eq(after.stakedAmount.toNumber());
+      expect(dropped).to.be.greaterThan(0);
+
+      const globalAfter = await program.account.globalAccount.fetch(globalPDAAddress);
+      const poolAfter = await program.account.rewardPool.fetch(rewardPoolPDA);
+      expect(globalAfter.totalWeightedStake.toNumber()).to.eq(globalBefore.totalWeightedStake.toNumber() - dropped);
+      expect(poolAfter.totalWeightedStake.toNumber()).to.eq(poolBefore.totalWeightedStake.toNumber() - dropped);
+  });
+
+  it("User 1 Requests a Partial Unstake of an Expired Position", async () => {
+      const position = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      const waitMs = (position.lockEndTime.toNumber() + 2) * 1000 - Date.now();
+      if (waitMs > 0) {
+          await new Promise(resolve => setTimeout(resolve, waitMs));
+      }
+
+      await program.methods.requestUnstake(new BN(0), new BN(40))
+        .accounts({
+            daoConfig: daoConfigPDA,
+            globalAccount: globalPDAAddress,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: stakeRecordPDA,
+            user: user1.publicKey,
+        })
+        .signers([user1])
+        .rpc();
+
+      const after = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      expect(after.stakedAmount.toNumber()).to.eq(position.stakedAmount.toNumber() - 40);
+      expect(after.unbondingAmount.toNumber()).to.eq(40);
+  });
+
+  it("Cannot Withdraw Before Unbonding Ends", async () => {
+      try {
+          await program.methods.withdrawUnstaked(new BN(0))
+            .accounts({
+                globalAccount: globalPDAAddress,
+                stakeRecord: stakeRecordPDA,
+                vault: vaultPDAAddress,
+                tokenMint: mint,
+                userTokenAccount: user1ATA,
+                user: user1.publicKey,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have failed with UnbondingInProgress");
+      } catch(e) {
+          expect(e.message).to.include("UnbondingInProgress");
+      }
+  });
+
+  it("User 1 Withdraws After the Unbonding Period", async () => {
+      const position = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      const waitMs = (position.unbondingEndTime.toNumber() + 2) * 1000 - Date.now();
+      if (waitMs > 0) {
+          await new Promise(resolve => setTimeout(resolve, waitMs));
+      }
+
+      const balBefore = await provider.connection.getTokenAccountBalance(user1ATA);
+
+      await program.methods.withdrawUnstaked(new BN(0))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            stakeRecord: stakeRecordPDA,
+            vault: vaultPDAAddress,
+            tokenMint: mint,
+            userTokenAccount: user1ATA,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+
+      const after = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      expect(after.unbondingAmount.toNumber()).to.eq(0);
+
+      const balAfter = await provider.connection.getTokenAccountBalance(user1ATA);
+      expect(parseInt(balAfter.value.amount)).to.eq(parseInt(balBefore.value.amount) + 40);
+  });
+
+  it("Cannot Unstake More Than the Staked Balance", async () => {
+      try {
+          await program.methods.requestUnstake(new BN(0), new BN(1_000_000))
+            .accounts({
+                daoConfig: daoConfigPDA,
+                globalAccount: globalPDAAddress,
+                stakerProfile: stakerProfilePDA,
+                rewardPool: rewardPoolPDA,
+                stakeRecord: stakeRecordPDA,
+                user: user1.publicKey,
+            })
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have failed with InsufficientStakedBalance");
+      } catch(e) {
+          expect(e.message).to.include("InsufficientStakedBalance");
+      }
+  });
+
+  // =========================================================================
+  // EARLY UNSTAKE
+  // =========================================================================
+
+  it("User 1 Exits a Locked Position Early and Pays a Penalty", async () => {
+      await mintTo(provider.connection, (owner as any).payer, mint, user1ATA, owner.publicKey, 100);
+
+      const profile = await program.account.stakerProfile.fetch(stakerProfilePDA);
+      const positionId = profile.positionCount;
+      const positionPDA = stakePositionPDA(user1.publicKey, positionId.toNumber());
+
+      await program.methods
+        .initializeStake()
+        .accounts({
+            stakerProfile: stakerProfilePDA,
+            stakeRecord: positionPDA,
+            user: user1.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+
+      await program.methods
+        .depositTokens(positionId, new BN(100), new BN(360))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: positionPDA,
+            vault: vaultPDAAddress,
+            tokenMint: mint,
+            userTokenAccount: user1ATA,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user1])
+        .rpc();
+      // Delegating and revoking settle every position User 1 opened
+      user1Positions.push(positionPDA);
+
+      const treasuryBefore = await provider.connection.getTokenAccountBalance(treasuryVaultPDA);
+
+      await program.methods
+        .earlyUnstake(positionId)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            daoConfig: daoConfigPDA,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: positionPDA,
+            vault: vaultPDAAddress,
+            treasuryVault: treasuryVaultPDA,
+            tokenMint: mint,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user1])
+        .rpc();
+
+      const treasuryAfter = await provider.connection.getTokenAccountBalance(treasuryVaultPDA);
+      const penalty = parseInt(treasuryAfter.value.amount) - parseInt(treasuryBefore.value.amount);
+      const position = await program.account.voterStakeRecord.fetch(positionPDA);
+
+      // Almost the whole lock remains, so close to the full 50% penalty applies.
+      // The rest goes through the normal unbonding period.
+      expect(penalty).to.be.within(45, 50);
+      expect(position.unbondingAmount.toNumber() + penalty).to.eq(100);
+      expect(position.stakedAmount.toNumber()).to.eq(0);
+      expect(position.multiplier.toNumber()).to.eq(10000);
+  });
+
+  // =========================================================================
+  // STAKING REWARDS
+  // =========================================================================
+
+  it("Admin Funds the Reward Pool from the Treasury", async () => {
+      await mintTo(provider.connection, (owner as any).payer, mint, treasuryVaultPDA, owner.publicKey, 1000);
+      const treasury = await provider.connection.getTokenAccountBalance(treasuryVaultPDA);
+
+      await program.methods
+        .fundRewardPool(new BN(treasury.value.amount))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            treasuryVault: treasuryVaultPDA,
+            rewardVault: rewardVaultPDA,
+            tokenMint: mint,
+            admin: owner.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const rewards = await provider.connection.getTokenAccountBalance(rewardVaultPDA);
+      expect(rewards.value.amount).to.eq(treasury.value.amount);
+  });
+
+  const [candidateProfilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("delegate_profile"), candidate.publicKey.toBuffer()],
+      program.programId
+  );
+  const [user1DelegationPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("delegation_record"), user1.publicKey.toBuffer()],
+      program.programId
+  );
+  let candidateATA: anchor.web3.PublicKey;
+
+  it("Delegate Sets a Commission Capped by Config", async () => {
+      try {
+          await program.methods.setDelegateCommission(2500)
+            .accounts({ daoConfig: daoConfigPDA, delegateProfile: candidateProfilePDA, user: candidate.publicKey })
+            .signers([candidate])
+            .rpc();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("CommissionTooHigh");
+      }
+
+      await program.methods.setDelegateCommission(2000)
+        .accounts({ daoConfig: daoConfigPDA, delegateProfile: candidateProfilePDA, user: candidate.publicKey })
+        .signers([candidate])
+        .rpc();
+
+      const profile = await program.account.delegateProfile.fetch(candidateProfilePDA);
+      expect(profile.pendingCommissionBps).to.eq(2000);
+      expect(profile.commissionEffectiveAt.toNumber()).to.be.greaterThan(0);
+
+      // User 1 delegates to the candidate, who now earns a cut of their rewards
+      await program.methods.delegateVote(openTerms)
+        .accounts({
+            delegationRecord: user1DelegationPDA,
+            targetDelegate: candidate.publicKey,
+            targetDelegateProfile: candidateProfilePDA,
+            user: user1.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId
+        })
+        .remainingAccounts(positionAccounts(user1Positions))
+        .signers([user1])
+        .rpc();
+
+      candidateATA = (await getOrCreateAssociatedTokenAccount(provider.connection, (owner as any).payer, mint, candidate.publicKey)).address;
+
+      const record = await program.account.delegationRecord.fetch(user1DelegationPDA);
+      expect(record.commissionBps).to.eq(2000);
+
+      // Commission is charged on rewards earned from here on
+      await program.methods.setRewardRate(new BN(50))
+        .accounts({ globalAccount: globalPDAAddress, rewardPool: rewardPoolPDA, admin: owner.publicKey })
+        .rpc();
+      await new Promise(resolve => setTimeout(resolve, 3000));
+  });
+
+  it("User 1 Claims Rewards Accrued on Position #0", async () => {
+      const balBefore = await provider.connection.getTokenAccountBalance(user1ATA);
+      const commissionBefore = await provider.connection.getTokenAccountBalance(candidateATA);
+
+      // Commission is mandatory while delegating
+      try {
+          await program.methods
+            .claimRewards(new BN(0))
+            .accounts({
+                rewardPool: rewardPoolPDA,
+                stakeRecord: stakeRecordPDA,
+                rewardVault: rewardVaultPDA,
+                userTokenAccount: user1ATA,
+                delegationRecord: user1DelegationPDA,
+                tokenMint: mint,
+                user: user1.publicKey,
+                tokenProgram: TOKEN_PROGRAM_ID,
+            })
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("InvalidDelegate");
+      }
+
+      await program.methods
+        .claimRewards(new BN(0))
+        .accounts({
+            rewardPool: rewardPoolPDA,
+            stakeRecord: stakeRecordPDA,
+            rewardVault: rewardVaultPDA,
+            userTokenAccount: user1ATA,
+            delegationRecord: user1DelegationPDA,
+            tokenMint: mint,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(commissionAccounts(candidateProfilePDA, candidateATA))
+        .signers([user1])
+        .rpc();
+
+      const balAfter = await provider.connection.getTokenAccountBalance(user1ATA);
+      const commissionAfter = await provider.connection.getTokenAccountBalance(candidateATA);
+      const received = parseInt(balAfter.value.amount) - parseInt(balBefore.value.amount);
+      const commission = parseInt(commissionAfter.value.amount) - parseInt(commissionBefore.value.amount);
+      expect(received).to.be.greaterThan(0);
+      expect(commission).to.be.greaterThan(0);
+      expect(commission).to.be.at.most(Math.ceil((received + commission) * 0.2));
+
+      const position = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      expect(position.pendingRewards.toNumber()).to.eq(0);
+      expect(position.pendingCommission.toNumber()).to.eq(0);
+  });
+
+  it("User 1 Compounds Rewards into Position #1", async () => {
+      const before = await program.account.voterStakeRecord.fetch(user1Positions[1]);
+
+      await program.methods
+        .compoundRewards(new BN(1))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: user1Positions[1],
+            rewardVault: rewardVaultPDA,
+            vault: vaultPDAAddress,
+            delegationRecord: user1DelegationPDA,
+            tokenMint: mint,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(commissionAccounts(candidateProfilePDA, candidateATA))
+        .signers([user1])
+        .rpc();
+
+      const after = await program.account.voterStakeRecord.fetch(user1Positions[1]);
+      expect(after.stakedAmount.toNumber()).to.be.greaterThan(before.stakedAmount.toNumber());
+      expect(after.rewardWeight.toNumber()).to.be.greaterThan(before.rewardWeight.toNumber());
+  });
+
+  it("Revoking Pays the Commission Charged So Far", async () => {
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      const commissionBefore = await provider.connection.getTokenAccountBalance(candidateATA);
+
+      await program.methods.revokeDelegation()
+        .accounts({
+            delegationRecord: user1DelegationPDA,
+            rewardVault: rewardVaultPDA,
+            tokenMint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            user: user1.publicKey,
+        })
+        .remainingAccounts([
+            ...commissionAccounts(candidateProfilePDA, candidateATA),
+            ...positionAccounts(user1Positions),
+        ])
+        .signers([user1])
+        .rpc();
+
+      const commissionAfter = await provider.connection.getTokenAccountBalance(candidateATA);
+      expect(parseInt(commissionAfter.value.amount)).to.be.greaterThan(parseInt(commissionBefore.value.amount));
+      for (const positionPDA of user1Positions) {
+          const position = await program.account.voterStakeRecord.fetch(positionPDA);
+          expect(position.pendingCommission.toNumber()).to.eq(0);
+      }
+
+      // Rewards earned after the delegation ends carry no commission
+      await program.methods.setRewardRate(new BN(1))
+        .accounts({ globalAccount: globalPDAAddress, rewardPool: rewardPoolPDA, admin: owner.publicKey })
+        .rpc();
+  });
+
+  // =========================================================================
+  // COUNCIL
+  // =========================================================================
+
+  const [councilPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("council")],
+      program.programId
+  );
+  const electionPDA = (number: number) => {
+      const buffer = Buffer.alloc(8);
+      buffer.writeBigUInt64LE(BigInt(number));
+      return anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("council_election"), buffer],
+          program.programId
+      )[0];
+  };
+
+  it("Token Holders Elect a Council Member", async () => {
+      await program.methods.initializeCouncil(1, new BN(30))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            council: councilPDA,
+            admin: owner.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      // Candidates must be active delegates
+      const profileOf = (wallet: anchor.web3.PublicKey) => anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("delegate_profile"), wallet.toBuffer()],
+          program.programId
+      )[0];
+
+      // User 2 was removed as a delegate earlier and registers again
+      await program.methods.registerDelegate()
+        .accounts({
+            globalAccount: globalPDAAddress,
+            delegateProfile: profileOf(user2.publicKey),
+            targetUser: user2.publicKey,
+            admin: owner.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      // Only stake locked past the deadline counts, so User 1 locks a new position
+      await mintTo(provider.connection, (owner as any).payer, mint, user1ATA, owner.publicKey, 25);
+      const lockedPositionPDA = stakePositionPDA(user1.publicKey, user1Positions.length);
+      await program.methods.initializeStake()
+        .accounts({
+            stakerProfile: stakerProfilePDA,
+            stakeRecord: lockedPositionPDA,
+            user: user1.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+      await program.methods.depositTokens(new BN(user1Positions.length), new BN(25), new BN(365))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: lockedPositionPDA,
+            vault: vaultPDAAddress,
+            tokenMint: mint,
+            userTokenAccount: user1ATA,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user1])
+        .rpc();
+      user1Positions.push(lockedPositionPDA);
+
+      const election = electionPDA(1);
+      const openElection = () => program.methods
+        .openCouncilElection(new BN(Math.floor(Date.now() / 1000) + 4))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            council: councilPDA,
+            election,
+            payer: owner.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await openElection();
+
+      const stand = (wallet: anchor.web3.Keypair) => program.methods.standForCouncil()
+        .accounts({ election, candidateProfile: profileOf(wallet.publicKey), candidate: wallet.publicKey })
+        .signers([wallet])
+        .rpc();
+      try {
+          await stand(user1);
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("AccountNotInitialized");
+      }
+
+      for (const wallet of [user2, candidate]) {
+          await stand(wallet);
+      }
+
+      const [ballotPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("council_ballot"), election.toBuffer(), user1.publicKey.toBuffer()],
+          program.programId
+      );
+      const castBallot = () => program.methods.voteCouncilElection(user2.publicKey)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            election,
+            ballot: ballotPDA,
+            user: user1.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(positionAccounts(user1Positions))
+        .signers([user1])
+        .rpc();
+      await castBallot();
+
+      // One ballot per holder
+      try {
+          await castBallot();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("already in use");
+      }
+
+      const finalize = () => program.methods.finalizeCouncilElection()
+        .accounts({ council: councilPDA, election })
+        .rpc();
+      try {
+          await finalize();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("ProposalNotEnded");
+      }
+
+      await new Promise(resolve => setTimeout(resolve, 5000));
+      await finalize();
+
+      const council = await program.account.council.fetch(councilPDA);
+      expect(council.seats[0].member.toString()).to.equal(user2.publicKey.toString());
+      expect(council.seats[0].termEnd.toNumber()).to.be.greaterThan(Math.floor(Date.now() / 1000));
+      expect(council.electionOpen).to.be.false;
+
+      const ballot = await program.account.councilBallot.fetch(ballotPDA);
+      const result = await program.account.councilElection.fetch(election);
+      expect(result.finalized).to.be.true;
+      expect(result.candidates[0].votes.toNumber()).to.equal(ballot.votingPower.toNumber());
+
+      // The locked position is reserved to User 1's ballot until the deadline
+      const locked = await program.account.voterStakeRecord.fetch(lockedPositionPDA);
+      expect(locked.voteHolder.toString()).to.equal(user1.publicKey.toString());
+      expect(locked.voteReservedUntil.toNumber()).to.equal(result.deadline.toNumber());
+  });
+
+  it("Council Majority Fast-Tracks and Vetoes Proposals", async () => {
+      const createProposal = async (title: string, emergency: boolean) => {
+          const globalAccount = await program.account.globalAccount.fetch(globalPDAAddress);
+          const buffer = Buffer.alloc(8);
+          buffer.writeBigUInt64LE(BigInt(globalAccount.proposalCount.toNumber() + 1));
+          const [pda] = anchor.web3.PublicKey.findProgramAddressSync(
+              [Buffer.from("proposal"), buffer],
+              program.programId
+          );
+          await program.methods
+            .createProposal(title, "Description", new BN(Math.floor(Date.now() / 1000) + 60), emergency)
+            .accounts({
+                globalAccount: globalPDAAddress,
+                proposalAccount: pda,
+                author: owner.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .rpc();
+          return pda;
+      };
+      const emergencyPDA = await createProposal("Emergency Proposal", true);
+      const contestedPDA = await createProposal("Contested Proposal", false);
+      const councilAccounts = (proposal: anchor.web3.PublicKey, member: anchor.web3.PublicKey) => ({
+          council: councilPDA,
+          proposalAccount: proposal,
+          motion: anchor.web3.PublicKey.findProgramAddressSync(
+              [Buffer.from("council_motion"), proposal.toBuffer()],
+              program.programId
+          )[0],
+          member,
+          systemProgram: anchor.web3.SystemProgram.programId,
+      });
+
+      // Only sitting members hold council powers
+      try {
+          await program.methods.vetoProposal()
+            .accounts(councilAccounts(contestedPDA, user1.publicKey))
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("NotCouncilMember");
+      }
+
+      // Only proposals flagged as emergencies at creation can be fast-tracked
+      try {
+          await program.methods.fastTrackProposal()
+            .accounts(councilAccounts(contestedPDA, user2.publicKey))
+            .signers([user2])
+            .rpc();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("NotEmergencyProposal");
+      }
+
+      // User 2 holds the only seat, so their approval is a majority
+      const before = await program.account.proposalAccount.fetch(emergencyPDA);
+      const fastTrack = () => program.methods.fastTrackProposal()
+        .accounts(councilAccounts(emergencyPDA, user2.publicKey))
+        .signers([user2])
+        .rpc();
+      await fastTrack();
+      const fastTracked = await program.account.proposalAccount.fetch(emergencyPDA);
+      expect(fastTracked.deadline.toNumber()).to.be.lessThan(before.deadline.toNumber());
+      expect(fastTracked.timelockSeconds.toNumber()).to.equal(0);
+
+      const motion = await program.account.councilMotion.fetch(councilAccounts(emergencyPDA, user2.publicKey).motion);
+      expect(motion.fastTrackApprovals.map((m: anchor.web3.PublicKey) => m.toString())).to.deep.equal([user2.publicKey.toString()]);
+
+      // A member's approval counts once
+      try {
+          await fastTrack();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("AlreadyVoted");
+      }
+
+      await program.methods.vetoProposal()
+        .accounts(councilAccounts(contestedPDA, user2.publicKey))
+        .signers([user2])
+        .rpc();
+      const vetoed = await program.account.proposalAccount.fetch(contestedPDA);
+      expect(vetoed.vetoed).to.be.true;
+      expect(vetoed.isActive).to.be.false;
+
+      // Voting on a vetoed proposal is closed
+      try {
+          await program.methods.vote(true)
+            .accounts({
+                globalAccount: globalPDAAddress,
+                proposalAccount: contestedPDA,
+                voterRecord: anchor.web3.PublicKey.findProgramAddressSync(
+                    [Buffer.from("voter"), contestedPDA.toBuffer(), user1.publicKey.toBuffer()],
+                    program.programId
+                )[0],
+                userTokenAccount: user1ATA,
+                user: user1.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .remainingAccounts(positionAccounts(user1Positions))
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("ProposalNotActive");
+      }
+  });
+
+  it("Council Member Registers, Suspends and Reinstates a Delegate", async () => {
+      const newDelegate = anchor.web3.Keypair.generate();
+      const [profilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("delegate_profile"), newDelegate.publicKey.toBuffer()],
+          program.programId
+      );
+
+      await program.methods.registerDelegate()
+        .accounts({
+            globalAccount: globalPDAAddress,
+            delegateProfile: profilePDA,
+            targetUser: newDelegate.publicKey,
+            admin: user2.publicKey,
+            council: councilPDA,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user2])
+        .rpc();
+
+      const profile = await program.account.delegateProfile.fetch(profilePDA);
+      expect(profile.isActive).to.be.true;
+      expect(profile.authority.toString()).to.equal(newDelegate.publicKey.toString());
+
+      const statusAccounts = {
+          globalAccount: globalPDAAddress,
+          delegateProfile: profilePDA,
+          targetUser: newDelegate.publicKey,
+          council: councilPDA,
+          admin: user2.publicKey,
+      };
+      await program.methods.suspendDelegate("Council review")
+        .accounts(statusAccounts)
+        .signers([user2])
+        .rpc();
+      expect((await program.account.delegateProfile.fetch(profilePDA)).isActive).to.be.false;
+
+      await program.methods.reinstateDelegate("Cleared")
+        .accounts(statusAccounts)
+        .signers([user2])
+        .rpc();
+      expect((await program.account.delegateProfile.fetch(profilePDA)).isActive).to.be.true;
+  });
+
+  // =========================================================================
+  // POSITION NFTS
+  // =========================================================================
+
+  it("User 1 Tokenizes a Position and Sells It to User 2", async () => {
+      const positionId = new BN(2); // Early-unstaked position, still unbonding
+      const positionPDA = stakePositionPDA(user1.publicKey, positionId.toNumber());
+
+      const [positionMint] = await anchor.web3.PublicKey.findProgramAddress(
+          [Buffer.from("position_mint"), positionPDA.toBuffer()],
+          program.programId
+      );
+      const user1NftAccount = await getAssociatedTokenAddress(positionMint, user1.publicKey);
+
+      await program.methods
+        .tokenizePosition(positionId)
+        .accounts({
+            stakeRecord: positionPDA,
+            positionMint,
+            positionTokenAccount: user1NftAccount,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user1])
+        .rpc();
+
+      const position = await program.account.voterStakeRecord.fetch(positionPDA);
+      expect(position.positionMint.toBase58()).to.eq(positionMint.toBase58());
+
+      const user2NftAccount = (await getOrCreateAssociatedTokenAccount(
+          provider.connection, (owner as any).payer, positionMint, user2.publicKey
+      )).address;
+      await transfer(provider.connection, (owner as any).payer, user1NftAccount, user2NftAccount, user1, 1);
+
+      const waitMs = (position.unbondingEndTime.toNumber() + 2) * 1000 - Date.now();
+      if (waitMs > 0) {
+          await new Promise(resolve => setTimeout(resolve, waitMs));
+      }
+
+      // The original wallet no longer controls the position
+      try {
+          await program.methods.withdrawUnstaked(positionId)
+            .accounts({
+                globalAccount: globalPDAAddress,
+                stakeRecord: positionPDA,
+                positionTokenAccount: user1NftAccount,
+                vault: vaultPDAAddress,
+                tokenMint: mint,
+                userTokenAccount: user1ATA,
+                user: user1.publicKey,
+                tokenProgram: TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            })
+            .signers([user1])
+            .rpc();
+          expect.fail("Should have failed with Unauthorized");
+      } catch(e) {
+          expect(e.message).to.include("Unauthorized");
+      }
+
+      const balBefore = await provider.connection.getTokenAccountBalance(user2ATA);
+
+      await program.methods.withdrawUnstaked(positionId)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            stakeRecord: positionPDA,
+            positionTokenAccount: user2NftAccount,
+            vault: vaultPDAAddress,
+            tokenMint: mint,
+            userTokenAccount: user2ATA,
+            user: user2.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user2])
+        .rpc();
+
+      const balAfter = await provider.connection.getTokenAccountBalance(user2ATA);
+      expect(parseInt(balAfter.value.amount)).to.eq(
+          parseInt(balBefore.value.amount) + position.unbondingAmount.toNumber()
+      );
+  });
+
+  it("A Sold Position Cannot Vote Again While Its Votes Are Live", async () => {
+      await mintTo(provider.connection, (owner as any).payer, mint, user1ATA, owner.publicKey, 10);
+
+      const profile = await program.account.stakerProfile.fetch(stakerProfilePDA);
+      const positionId = profile.positionCount;
+      const positionPDA = stakePositionPDA(user1.publicKey, positionId.toNumber());
+      await program.methods.initializeStake()
+        .accounts({
+            stakerProfile: stakerProfilePDA,
+            stakeRecord: positionPDA,
+            user: user1.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user1])
+        .rpc();
+      await program.methods.depositTokens(positionId, new BN(10), new BN(30))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: positionPDA,
+            vault: vaultPDAAddress,
+            tokenMint: mint,
+            userTokenAccount: user1ATA,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user1])
+        .rpc();
+
+      const [positionMint] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("position_mint"), positionPDA.toBuffer()],
+          program.programId
+      );
+      const user1NftAccount = await getAssociatedTokenAddress(positionMint, user1.publicKey);
+      await program.methods.tokenizePosition(positionId)
+        .accounts({
+            stakeRecord: positionPDA,
+            positionMint,
+            positionTokenAccount: user1NftAccount,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user1])
+        .rpc();
+
+      const globalAccount = await program.account.globalAccount.fetch(globalPDAAddress);
+      const buffer = Buffer.alloc(8);
+      buffer.writeBigUInt64LE(BigInt(globalAccount.proposalCount.toNumber() + 1));
+      const [proposalPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("proposal"), buffer],
+          program.programId
+      );
+      await program.methods
+        .createProposal("Resale Test Proposal", "Description", new BN(Math.floor(Date.now() / 1000) + 60), false)
+        .accounts({
+            globalAccount: globalPDAAddress,
+            proposalAccount: proposalPDA,
+            author: owner.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const voteWithPosition = (voter: anchor.web3.Keypair, voterATA: anchor.web3.PublicKey, nftAccount: anchor.web3.PublicKey) =>
+          program.methods.vote(true)
+            .accounts({
+                globalAccount: globalPDAAddress,
+                proposalAccount: proposalPDA,
+                voterRecord: anchor.web3.PublicKey.findProgramAddressSync(
+                    [Buffer.from("voter"), proposalPDA.toBuffer(), voter.publicKey.toBuffer()],
+                    program.programId
+                )[0],
+                userTokenAccount: voterATA,
+                user: voter.publicKey,
+            })
+            .remainingAccounts([
+                { pubkey: positionPDA, isWritable: true, isSigner: false },
+                { pubkey: nftAccount, isWritable: false, isSigner: false },
+            ])
+            .signers([voter])
+            .rpc();
+
+      await voteWithPosition(user1, user1ATA, user1NftAccount);
+      const reserved = await program.account.voterStakeRecord.fetch(positionPDA);
+      expect(reserved.voteHolder.toString()).to.equal(user1.publicKey.toString());
+
+      const user2NftAccount = (await getOrCreateAssociatedTokenAccount(
+          provider.connection, (owner as any).payer, positionMint, user2.publicKey
+      )).address;
+      await transfer(provider.connection, (owner as any).payer, user1NftAccount, user2NftAccount, user1, 1);
+
+      // The buyer cannot count the same stake on the live proposal again
+      try {
+          await voteWithPosition(user2, user2ATA, user2NftAccount);
+          expect.fail("Should have failed");
+      } catch(e) {
+          expect(e.message).to.include("PositionVoteReserved");
+      }
+  });
+
+  it("User 1 Delegates the Positions They Kept, With No Commission on the Ones Sold", async () => {
+      // Positions #2 and the one sold above belong to User 2's NFTs now
+      const profile = await program.account.stakerProfile.fetch(stakerProfilePDA);
+      const opened = [...Array(profile.positionCount.toNumber()).keys()].map((id) => stakePositionPDA(user1.publicKey, id));
+      const soldPositionPDA = opened[opened.length - 1];
+      let kept = 0;
+      for (const positionPDA of opened) {
+          const position = await program.account.voterStakeRecord.fetch(positionPDA);
+          if (position.positionMint.equals(anchor.web3.PublicKey.default)) {
+              kept += position.stakedAmount.toNumber();
+          }
+      }
+      await program.methods.setRewardRate(new BN(50))
+        .accounts({ globalAccount: globalPDAAddress, rewardPool: rewardPoolPDA, admin: owner.publicKey })
+        .rpc();
+
+      await program.methods.delegateVote(openTerms)
+        .accounts({
+            delegationRecord: user1DelegationPDA,
+            targetDelegate: candidate.publicKey,
+            targetDelegateProfile: candidateProfilePDA,
+            user: user1.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId
+        })
+        .remainingAccounts(positionAccounts(opened))
+        .signers([user1])
+        .rpc();
+
+      const record = await program.account.delegationRecord.fetch(user1DelegationPDA);
+      expect(record.delegatedStake.toNumber()).to.eq(kept);
+      expect(record.commissionBps).to.be.greaterThan(0);
+      await new Promise(resolve => setTimeout(resolve, 3000));
+
+      // The buyer's rewards owe nothing to the seller's delegate
+      const sold = await program.account.voterStakeRecord.fetch(soldPositionPDA);
+      const user2NftAccount = await getAssociatedTokenAddress(sold.positionMint, user2.publicKey);
+      const balBefore = await provider.connection.getTokenAccountBalance(user2ATA);
+      await program.methods
+        .claimRewards(sold.positionId)
+        .accounts({
+            rewardPool: rewardPoolPDA,
+            stakeRecord: soldPositionPDA,
+            positionTokenAccount: user2NftAccount,
+            rewardVault: rewardVaultPDA,
+            userTokenAccount: user2ATA,
+            delegationRecord: user1DelegationPDA,
+            tokenMint: mint,
+            user: user2.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user2])
+        .rpc();
+      const balAfter = await provider.connection.getTokenAccountBalance(user2ATA);
+      expect(parseInt(balAfter.value.amount)).to.be.greaterThan(parseInt(balBefore.value.amount));
+      expect((await program.account.voterStakeRecord.fetch(soldPositionPDA)).pendingCommission.toNumber()).to.eq(0);
+
+      await program.methods.revokeDelegation()
+        .accounts({
+            delegationRecord: user1DelegationPDA,
+            rewardVault: rewardVaultPDA,
+            tokenMint: mint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            user: user1.publicKey,
+        })
+        .remainingAccounts([
+            ...commissionAccounts(candidateProfilePDA, candidateATA),
+            ...positionAccounts(opened),
+        ])
+        .signers([user1])
+        .rpc();
+
+      // Revoking settles the sold position without commission as well
+      const settled = await program.account.voterStakeRecord.fetch(soldPositionPDA);
+      expect(settled.pendingCommission.toNumber()).to.eq(0);
+
+      await program.methods.setRewardRate(new BN(1))
+        .accounts({ globalAccount: globalPDAAddress, rewardPool: rewardPoolPDA, admin: owner.publicKey })
+        .rpc();
+  });
+
+  // =========================================================================
+  // EMERGENCY EXIT
+  // =========================================================================
+
+  const emergencyWithdrawPosition1 = () =>
+      program.methods.emergencyWithdraw(new BN(1))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: user1Positions[1],
+            vault: vaultPDAAddress,
+            userTokenAccount: user1ATA,
+            tokenMint: mint,
+            user: user1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user1])
+        .rpc();
+
+  it("Cannot Emergency Withdraw While the System Is Running", async () => {
+      try {
+          await emergencyWithdrawPosition1();
+          expect.fail("Should have failed with EmergencyExitNotActive");
+      } catch(e) {
+          expect(e.message).to.include("EmergencyExitNotActive");
+      }
+  });
+
+  it("User 1 Exits a Locked Position During an Emergency", async () => {
+      await program.methods.toggleCircuitBreaker()
+        .accounts({ globalAccount: globalPDAAddress, user: owner.publicKey })
+        .rpc();
+      await program.methods.toggleEmergencyExit()
+        .accounts({ globalAccount: globalPDAAddress, user: owner.publicKey })
+        .rpc();
+
+      const position = await program.account.voterStakeRecord.fetch(user1Positions[1]);
+      expect(position.lockEndTime.toNumber()).to.be.greaterThan(Date.now() / 1000);
+      expect(position.rewardWeight.toNumber()).to.be.greaterThan(0);
+      const balBefore = await provider.connection.getTokenAccountBalance(user1ATA);
+      // Both accounts exist by now, so the exit must update them in place
+      const poolBefore = await program.account.rewardPool.fetch(rewardPoolPDA);
+      const profileBefore = await program.account.stakerProfile.fetch(stakerProfilePDA);
+
+      await emergencyWithdrawPosition1();
+
+      const balAfter = await provider.connection.getTokenAccountBalance(user1ATA);
+      expect(parseInt(balAfter.value.amount)).to.eq(
+          parseInt(balBefore.value.amount) + position.stakedAmount.toNumber() + position.unbondingAmount.toNumber()
+      );
+
+      const after = await program.account.voterStakeRecord.fetch(user1Positions[1]);
+      expect(after.stakedAmount.toNumber()).to.eq(0);
+      expect(after.multiplier.toNumber()).to.eq(10000);
+      expect(after.rewardWeight.toNumber()).to.eq(0);
+
+      const poolAfter = await program.account.rewardPool.fetch(rewardPoolPDA);
+      expect(poolAfter.totalWeightedStake.toNumber()).to.eq(
+          poolBefore.totalWeightedStake.toNumber() - position.rewardWeight.toNumber()
+      );
+      const profileAfter = await program.account.stakerProfile.fetch(stakerProfilePDA);
+      expect(profileAfter.activePositions.toNumber()).to.eq(profileBefore.activePositions.toNumber() - 1);
+
+      await program.methods.toggleEmergencyExit()
+        .accounts({ globalAccount: globalPDAAddress, user: owner.publicKey })
+        .rpc();
+      await program.methods.toggleCircuitBreaker()
+        .accounts({ globalAccount: globalPDAAddress, user: owner.publicKey })
+        .rpc();
+
+      const state = await program.account.globalAccount.fetch(globalPDAAddress);
+      expect(state.systemEnabled).to.be.true;
+      expect(state.emergencyExitEnabled).to.be.false;
+  });
+
 });
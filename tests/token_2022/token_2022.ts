@@ -0,0 +1,221 @@
+import { expect } from "chai";
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "bn.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  getMint,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+
+// Runs on its own validator (see Test.toml): the DAO's token mint is fixed
+// when the global account is initialized.
+describe("Pulsar DAO with a Token-2022 Transfer-Fee Mint", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.PulsarDao;
+  const owner = (program.provider as anchor.AnchorProvider).wallet;
+  const user = anchor.web3.Keypair.generate();
+  const mintKeypair = anchor.web3.Keypair.generate();
+  const mint = mintKeypair.publicKey;
+
+  // 1% fee on every transfer, with no cap in practice
+  const FEE_BPS = 100;
+  const afterFee = (amount: number) => amount - Math.ceil(amount * FEE_BPS / 10000);
+
+  const [globalPDAAddress] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_account")],
+      program.programId
+  );
+  const [vaultPDAAddress] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), mint.toBuffer()],
+      program.programId
+  );
+  const [rewardPoolPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_pool")],
+      program.programId
+  );
+  const [daoConfigPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("dao_config")],
+      program.programId
+  );
+  const [delegationRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("delegation_record"), user.publicKey.toBuffer()],
+      program.programId
+  );
+  const [stakerProfilePDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("staker_profile"), user.publicKey.toBuffer()],
+      program.programId
+  );
+  const [stakeRecordPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_record"), user.publicKey.toBuffer(), new BN(0).toArrayLike(Buffer, "le", 8)],
+      program.programId
+  );
+  let userATA: anchor.web3.PublicKey;
+
+  before("Setup Environment", async () => {
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+            fromPubkey: provider.wallet.publicKey,
+            toPubkey: user.publicKey,
+            lamports: anchor.web3.LAMPORTS_PER_SOL,
+        })
+    ));
+
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    await provider.sendAndConfirm(new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+            fromPubkey: owner.publicKey,
+            newAccountPubkey: mint,
+            space: mintLen,
+            lamports: await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+            programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+            mint, owner.publicKey, owner.publicKey, FEE_BPS, BigInt(1_000_000), TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(mint, 0, owner.publicKey, null, TOKEN_2022_PROGRAM_ID),
+    ), [mintKeypair]);
+
+    userATA = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, (owner as any).payer, mint, user.publicKey, false, undefined, undefined, TOKEN_2022_PROGRAM_ID
+    )).address;
+    await mintTo(provider.connection, (owner as any).payer, mint, userATA, owner.publicKey, 2000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+    await program.methods
+      .initialize()
+      .accounts({
+        user: owner.publicKey,
+        tokenMint: mint,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("Stakes What Reaches the Vault After the Transfer Fee", async () => {
+      await program.methods
+        .initializeStake()
+        .accounts({
+            stakerProfile: stakerProfilePDA,
+            stakeRecord: stakeRecordPDA,
+            user: user.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      await program.methods
+        .depositTokens(new BN(0), new BN(1000), new BN(30))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: stakeRecordPDA,
+            vault: vaultPDAAddress,
+            tokenMint: mint,
+            userTokenAccount: userATA,
+            user: user.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+
+      const vault = await provider.connection.getTokenAccountBalance(vaultPDAAddress);
+      expect(parseInt(vault.value.amount)).to.eq(afterFee(1000));
+
+      const position = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      expect(position.stakedAmount.toNumber()).to.eq(afterFee(1000));
+
+      const global = await program.account.globalAccount.fetch(globalPDAAddress);
+      expect(global.totalStaked.toNumber()).to.eq(afterFee(1000));
+  });
+
+  it("Escrows What Reaches the Treasury Proposal After the Transfer Fee", async () => {
+      const number = new BN(1).toArrayLike(Buffer, "le", 8);
+      const [proposalPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("proposal"), number],
+          program.programId
+      );
+      const [proposalEscrowPDA] = anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("proposal_escrow"), number],
+          program.programId
+      );
+      const balBefore = await provider.connection.getTokenAccountBalance(userATA);
+
+      await program.methods
+        .createTreasuryProposal(
+          "Fee-bearing grant",
+          "Description",
+          new BN(Math.floor(Date.now() / 1000) + 60),
+          new BN(500),
+          anchor.web3.Keypair.generate().publicKey,
+          new BN(2),
+          false
+        )
+        .accounts({
+          globalAccount: globalPDAAddress,
+          proposalAccount: proposalPDA,
+          proposalEscrow: proposalEscrowPDA,
+          tokenMint: mint,
+          authorTokenAccount: userATA,
+          author: user.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([user])
+        .rpc();
+
+      // The author pays the full amount; the proposal only promises what arrived
+      const balAfter = await provider.connection.getTokenAccountBalance(userATA);
+      expect(parseInt(balBefore.value.amount) - parseInt(balAfter.value.amount)).to.eq(500);
+
+      const escrow = await provider.connection.getTokenAccountBalance(proposalEscrowPDA);
+      expect(parseInt(escrow.value.amount)).to.eq(afterFee(500));
+
+      const proposal = await program.account.proposalAccount.fetch(proposalPDA);
+      expect(proposal.transferAmount.toNumber()).to.eq(afterFee(500));
+  });
+
+  it("Exits Early With the Default Config Before initialize_config", async () => {
+      // This validator never runs initialize_config: the penalty is burned
+      // at the default rate, as there is no treasury vault yet
+      expect(await provider.connection.getAccountInfo(daoConfigPDA)).to.be.null;
+      const supplyBefore = (await getMint(provider.connection, mint, undefined, TOKEN_2022_PROGRAM_ID)).supply;
+
+      await program.methods
+        .earlyUnstake(new BN(0))
+        .accounts({
+            globalAccount: globalPDAAddress,
+            daoConfig: daoConfigPDA,
+            stakerProfile: stakerProfilePDA,
+            rewardPool: rewardPoolPDA,
+            stakeRecord: stakeRecordPDA,
+            positionTokenAccount: null,
+            delegationRecord: delegationRecordPDA,
+            vault: vaultPDAAddress,
+            treasuryVault: null,
+            tokenMint: mint,
+            user: user.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const position = await program.account.voterStakeRecord.fetch(stakeRecordPDA);
+      const penalty = afterFee(1000) - position.unbondingAmount.toNumber();
+      expect(penalty).to.be.greaterThan(0);
+      expect(position.unbondingEndTime.toNumber()).to.be.greaterThan(Date.now() / 1000);
+
+      const supplyAfter = (await getMint(provider.connection, mint, undefined, TOKEN_2022_PROGRAM_ID)).supply;
+      expect(Number(supplyBefore - supplyAfter)).to.eq(penalty);
+  });
+});